@@ -1,4 +1,5 @@
 use std::{
+    env,
     fs::{self, File},
     io::{
         prelude::*,
@@ -9,9 +10,16 @@ use std::{
 };
 
 use threadpool::ThreadPool;
-use lib::{grid, solver, translator};
+use lib::{generator, grid, solver, strategy::Difficulty, translator};
 
 fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    if args.get(1).map(|s| s.as_str()) == Some("generate") {
+        generate(&args[2..]);
+        return;
+    }
+
     let paths = [
         "../sudoku-exchange-puzzle-bank/easy.txt",
         "../sudoku-exchange-puzzle-bank/medium.txt",
@@ -91,3 +99,32 @@ fn solve_until_end(bd: &str) -> bool {
 
     solver.get_grid().is_complete()
 }
+
+// usage: cli generate <easy|medium|hard|diabolical> [count]
+fn generate(args: &[String]) {
+    let difficulty = match args.first().map(|s| s.as_str()) {
+        Some("easy") => Difficulty::Easy,
+        Some("medium") => Difficulty::Medium,
+        Some("hard") => Difficulty::Hard,
+        Some("diabolical") => Difficulty::Diabolical,
+        _ => {
+            println!("usage: cli generate <easy|medium|hard|diabolical> [count]");
+            return;
+        }
+    };
+
+    let count: usize = args.get(1).and_then(|s| s.parse().ok()).unwrap_or(1);
+
+    let seed = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos() as u64;
+
+    let mut generator = generator::Generator::from_seed(seed);
+
+    for _ in 0..count {
+        if let Some(puzzle) = generator.generate_with_difficulty(difficulty, 1000) {
+            println!("{}", puzzle.to_bd_str());
+        }
+    }
+}
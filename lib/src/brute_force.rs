@@ -0,0 +1,258 @@
+use crate::grid::{get_minigrid_n_from_coords, Grid};
+
+const ALL_DIGITS: u16 = 0b11_1111_1110;
+
+// Bitboard representation of the placed digits of a grid. Only the placed digits are used, any
+// candidate eliminations in the grid are ignored so the search finds solutions of the puzzle
+// itself.
+#[derive(Clone)]
+struct Board {
+    cells: [u32; 81],
+    rows: [u16; 9],
+    cols: [u16; 9],
+    minigrids: [u16; 9],
+}
+
+impl Board {
+    fn from_grid(grid: &Grid) -> Option<Board> {
+        let mut board = Board {
+            cells: [0; 81],
+            rows: [0; 9],
+            cols: [0; 9],
+            minigrids: [0; 9],
+        };
+
+        for r in 0..9 {
+            for c in 0..9 {
+                let val = grid.get_placed(r, c);
+
+                if val == 0 {
+                    continue;
+                }
+
+                let idx = (9 * r + c) as usize;
+
+                // two equal digits in the same unit, there is no solution
+                if board.free(idx) & (1 << val) == 0 {
+                    return None;
+                }
+
+                board.set(idx, val);
+            }
+        }
+
+        Some(board)
+    }
+
+    fn units(idx: usize) -> (usize, usize, usize) {
+        let row = idx / 9;
+        let col = idx % 9;
+        let minigrid = get_minigrid_n_from_coords(row as u32, col as u32) as usize;
+
+        (row, col, minigrid)
+    }
+
+    fn free(&self, idx: usize) -> u16 {
+        let (row, col, minigrid) = Board::units(idx);
+
+        ALL_DIGITS & !(self.rows[row] | self.cols[col] | self.minigrids[minigrid])
+    }
+
+    fn set(&mut self, idx: usize, val: u32) {
+        let (row, col, minigrid) = Board::units(idx);
+
+        self.cells[idx] = val;
+        self.rows[row] |= 1 << val;
+        self.cols[col] |= 1 << val;
+        self.minigrids[minigrid] |= 1 << val;
+    }
+
+    fn unset(&mut self, idx: usize) {
+        let (row, col, minigrid) = Board::units(idx);
+        let val = self.cells[idx];
+
+        self.cells[idx] = 0;
+        self.rows[row] &= !(1 << val);
+        self.cols[col] &= !(1 << val);
+        self.minigrids[minigrid] &= !(1 << val);
+    }
+
+    // the empty cell with the fewest digits that can still be placed in it
+    fn most_constrained(&self) -> Option<(usize, u16)> {
+        let mut best: Option<(usize, u16)> = None;
+
+        for idx in 0..81 {
+            if self.cells[idx] != 0 {
+                continue;
+            }
+
+            let free = self.free(idx);
+
+            if best.is_none_or(|(_, best_free)| free.count_ones() < best_free.count_ones()) {
+                best = Some((idx, free));
+
+                if free.count_ones() <= 1 {
+                    break;
+                }
+            }
+        }
+
+        best
+    }
+
+    fn to_grid(&self) -> Grid {
+        let bd: String = self.cells.iter().map(|val| val.to_string()).collect();
+
+        Grid::from_str(&bd).unwrap()
+    }
+}
+
+// Depth-first search over the board, calling on_solution for every complete board found. The
+// search stops early once on_solution returns true.
+fn search<F>(board: &mut Board, on_solution: &mut F) -> bool
+where
+    F: FnMut(&Board) -> bool,
+{
+    let Some((idx, free)) = board.most_constrained() else {
+        return on_solution(board);
+    };
+
+    for val in 1..10 {
+        if free & (1 << val) == 0 {
+            continue;
+        }
+
+        board.set(idx, val);
+        let stop = search(board, on_solution);
+        board.unset(idx);
+
+        if stop {
+            return true;
+        }
+    }
+
+    false
+}
+
+pub fn solve(grid: &Grid) -> Option<Grid> {
+    let mut board = Board::from_grid(grid)?;
+    let mut solution = None;
+
+    search(&mut board, &mut |solved| {
+        solution = Some(solved.to_grid());
+        true
+    });
+
+    solution
+}
+
+// Counts the solutions of the grid, stopping once limit solutions have been found. A limit of 2 is
+// enough to tell whether a puzzle has no solution, a unique solution or many solutions.
+pub fn count_solutions(grid: &Grid, limit: usize) -> usize {
+    let Some(mut board) = Board::from_grid(grid) else {
+        return 0;
+    };
+
+    if limit == 0 {
+        return 0;
+    }
+
+    let mut count = 0;
+
+    search(&mut board, &mut |_| {
+        count += 1;
+        count >= limit
+    });
+
+    count
+}
+
+pub fn has_unique_solution(grid: &Grid) -> bool {
+    count_solutions(grid, 2) == 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve() {
+        let bd =
+            "004005000010900340080002009705080020000203000090050801300500090076009010000300700";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let solution = solve(&grid).unwrap();
+
+        assert!(solution.is_complete());
+
+        for r in 0..9 {
+            for c in 0..9 {
+                let val = grid.get_placed(r, c);
+
+                if val != 0 {
+                    assert_eq!(val, solution.get_placed(r, c));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_solve_diabolical() {
+        let bd =
+            "100000002090400050006000700050903000000070000000850040700000600030009080002000001";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let expected =
+            "174385962293467158586192734451923876928674315367851249719548623635219487842736591";
+
+        let solution = solve(&grid).unwrap();
+
+        for (i, ch) in expected.chars().enumerate() {
+            let (r, c) = (i as u32 / 9, i as u32 % 9);
+
+            assert_eq!(ch.to_digit(10).unwrap(), solution.get_placed(r, c));
+        }
+    }
+
+    #[test]
+    fn test_solve_invalid() {
+        // two 4s in the first row
+        let bd =
+            "404005000010900340080002009705080020000203000090050801300500090076009010000300700";
+        let grid = Grid::from_str(bd).unwrap();
+
+        assert!(solve(&grid).is_none());
+    }
+
+    #[test]
+    fn test_count_solutions_unique() {
+        let bd =
+            "004005000010900340080002009705080020000203000090050801300500090076009010000300700";
+        let grid = Grid::from_str(bd).unwrap();
+
+        assert_eq!(1, count_solutions(&grid, 2));
+        assert!(has_unique_solution(&grid));
+    }
+
+    #[test]
+    fn test_count_solutions_many() {
+        // first row and last clue removed from the puzzle above
+        let bd =
+            "000000000010900340080002009705080020000203000090050801300500090076009010000300000";
+        let grid = Grid::from_str(bd).unwrap();
+
+        assert_eq!(2, count_solutions(&grid, 2));
+        assert_eq!(10, count_solutions(&grid, 10));
+        assert!(!has_unique_solution(&grid));
+    }
+
+    #[test]
+    fn test_count_solutions_invalid() {
+        let bd =
+            "404005000010900340080002009705080020000203000090050801300500090076009010000300700";
+        let grid = Grid::from_str(bd).unwrap();
+
+        assert_eq!(0, count_solutions(&grid, 2));
+        assert!(!has_unique_solution(&grid));
+    }
+}
@@ -0,0 +1,168 @@
+use crate::brute_force;
+use crate::grid::Grid;
+use crate::rating;
+use crate::strategy::Difficulty;
+use crate::util::Rng;
+
+pub struct Generator {
+    rng: Rng,
+}
+
+impl Generator {
+    pub fn from_seed(seed: u64) -> Generator {
+        Generator {
+            rng: Rng::from_seed(seed),
+        }
+    }
+
+    // Generates a puzzle with a unique solution. Clues are removed from a random full grid for as
+    // long as the puzzle stays unique, so every remaining clue is needed.
+    pub fn generate(&mut self) -> Grid {
+        let solution = self.generate_solution();
+
+        self.remove_clues(&solution)
+    }
+
+    // Keeps generating puzzles until the hardest strategy needed by the solver has the requested
+    // difficulty, giving up after max_attempts puzzles.
+    pub fn generate_with_difficulty(
+        &mut self,
+        difficulty: Difficulty,
+        max_attempts: usize,
+    ) -> Option<Grid> {
+        for _ in 0..max_attempts {
+            let puzzle = self.generate();
+
+            let puzzle_difficulty = rating::rate(&puzzle).map(|r| r.get_difficulty());
+
+            if puzzle_difficulty == Some(difficulty) {
+                return Some(puzzle);
+            }
+        }
+
+        None
+    }
+
+    fn generate_solution(&mut self) -> Grid {
+        let mut placed = vec![0; 81];
+
+        self.fill_cells(&mut placed, 0);
+
+        grid_from_placed(&placed)
+    }
+
+    // Fills the cells from idx on by backtracking, trying the digits of every cell in a random
+    // order so that any full grid can come out. An empty grid always has a solution, so this only
+    // fails when backtracking out of a dead end.
+    fn fill_cells(&mut self, placed: &mut [u32], idx: usize) -> bool {
+        if idx == 81 {
+            return true;
+        }
+
+        let mut digits: Vec<u32> = (1..10).collect();
+        self.rng.shuffle(&mut digits);
+
+        for val in digits {
+            if !can_place(placed, idx, val) {
+                continue;
+            }
+
+            placed[idx] = val;
+
+            if self.fill_cells(placed, idx + 1) {
+                return true;
+            }
+        }
+
+        placed[idx] = 0;
+
+        false
+    }
+
+    fn remove_clues(&mut self, solution: &Grid) -> Grid {
+        let mut placed: Vec<u32> = (0..81).map(|i| solution.get_placed(i / 9, i % 9)).collect();
+
+        let mut order: Vec<usize> = (0..81).collect();
+        self.rng.shuffle(&mut order);
+
+        for idx in order {
+            let val = placed[idx];
+            placed[idx] = 0;
+
+            if !brute_force::has_unique_solution(&grid_from_placed(&placed)) {
+                placed[idx] = val;
+            }
+        }
+
+        grid_from_placed(&placed)
+    }
+}
+
+// whether val is missing from the row, column and minigrid of the cell at idx
+fn can_place(placed: &[u32], idx: usize, val: u32) -> bool {
+    let (row, col) = (idx / 9, idx % 9);
+    let (cr, cc) = ((row / 3) * 3, (col / 3) * 3);
+
+    (0..9).all(|i| {
+        placed[9 * row + i] != val
+            && placed[9 * i + col] != val
+            && placed[9 * (cr + i / 3) + cc + i % 3] != val
+    })
+}
+
+fn grid_from_placed(placed: &[u32]) -> Grid {
+    let bd: String = placed.iter().map(|val| val.to_string()).collect();
+
+    Grid::from_str(&bd).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_unique() {
+        let mut generator = Generator::from_seed(1);
+
+        for _ in 0..5 {
+            let puzzle = generator.generate();
+
+            assert!(brute_force::has_unique_solution(&puzzle));
+        }
+    }
+
+    #[test]
+    fn test_generate_solution() {
+        let mut generator = Generator::from_seed(3);
+
+        let a = generator.generate_solution();
+        let b = generator.generate_solution();
+
+        assert!(a.is_complete() && brute_force::has_unique_solution(&a));
+        assert!(b.is_complete() && brute_force::has_unique_solution(&b));
+        assert_ne!(a.to_bd_str(), b.to_bd_str());
+    }
+
+    #[test]
+    fn test_generate_reproducible() {
+        let a = Generator::from_seed(42).generate();
+        let b = Generator::from_seed(42).generate();
+
+        assert_eq!(a.to_bd_str(), b.to_bd_str());
+    }
+
+    #[test]
+    fn test_generate_with_difficulty() {
+        let mut generator = Generator::from_seed(7);
+
+        let puzzle = generator
+            .generate_with_difficulty(Difficulty::Medium, 50)
+            .unwrap();
+
+        assert!(brute_force::has_unique_solution(&puzzle));
+        assert_eq!(
+            Some(Difficulty::Medium),
+            rating::rate(&puzzle).map(|r| r.get_difficulty())
+        );
+    }
+}
@@ -8,6 +8,8 @@ use crate::util::BitSet;
 #[derive(Clone, Serialize, Deserialize)]
 pub struct Grid {
     placed: Vec<u32>,
+    // the cells placed in the puzzle itself, as opposed to the ones placed while solving it
+    givens: Vec<bool>,
     candidates: Vec<BitSet>,
 }
 
@@ -33,8 +35,11 @@ impl Grid {
             }
         }
 
+        let givens = placed.iter().map(|&val| val != 0).collect();
+
         let mut grid = Grid {
             placed,
+            givens,
             candidates: vec![],
         };
         grid.autofill();
@@ -42,6 +47,10 @@ impl Grid {
         Ok(grid)
     }
 
+    pub fn to_bd_str(&self) -> String {
+        self.placed.iter().map(|val| val.to_string()).collect()
+    }
+
     fn get_placed_in_row(&self, row: u32) -> Vec<u32> {
         let mut nums = vec![];
 
@@ -158,6 +167,14 @@ impl Grid {
         }
     }
 
+    pub fn get_placed(&self, row: u32, col: u32) -> u32 {
+        self.placed[index(row, col)]
+    }
+
+    pub fn is_given(&self, row: u32, col: u32) -> bool {
+        self.givens[index(row, col)]
+    }
+
     pub fn get_candidates(&self, row: u32, col: u32) -> &BitSet {
         &self.candidates[index(row, col)]
     }
@@ -1,4 +1,7 @@
+pub mod brute_force;
+pub mod generator;
 pub mod grid;
+pub mod rating;
 pub mod solver;
 pub mod strategy;
 pub mod translator;
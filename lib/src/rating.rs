@@ -0,0 +1,167 @@
+use crate::grid::Grid;
+use crate::solver::Solver;
+use crate::strategy::{Difficulty, Strategy, StrategyResult};
+
+// Like Sudoku Explainer, a puzzle is rated by its hardest step rather than by the sum of its steps.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rating {
+    score: u32,
+    difficulty: Difficulty,
+}
+
+impl Rating {
+    pub fn get_score(&self) -> f32 {
+        self.score as f32 / 10.0
+    }
+
+    pub fn get_difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    pub fn get_label(&self) -> &str {
+        self.difficulty.to_str()
+    }
+}
+
+// Rates the grid by walking the steps taken by the solver, or returns None if the solver gets
+// stuck before the grid is complete.
+pub fn rate(grid: &Grid) -> Option<Rating> {
+    let mut solver = Solver::from(grid.clone());
+
+    let mut score = 0;
+    let mut difficulty = Difficulty::Easy;
+
+    while let Some((strategy, result)) = solver.step_with_strategy() {
+        score = score.max(get_step_weight(&strategy, &result));
+        difficulty = difficulty.max(strategy.get_difficulty());
+
+        solver.apply(&result);
+    }
+
+    if solver.get_grid().is_complete() {
+        Some(Rating { score, difficulty })
+    } else {
+        None
+    }
+}
+
+// weight of a single step in tenths of a rating point
+pub fn get_step_weight(strategy: &Strategy, result: &StrategyResult) -> u32 {
+    let bonus = match result.get_chain_length() {
+        Some(length) => get_chain_length_bonus(length),
+        None => 0,
+    };
+
+    strategy.get_weight() + bonus
+}
+
+// longer chains are harder to spot: a tenth of a point is added each time the length passes one of
+// 4, 6, 8, 12, 16, 24, 32, ...
+fn get_chain_length_bonus(length: usize) -> u32 {
+    let mut bonus = 0;
+    let mut threshold = 4;
+    let mut is_even_step = true;
+
+    while length > threshold {
+        bonus += 1;
+
+        threshold = if is_even_step {
+            threshold * 3 / 2
+        } else {
+            threshold * 4 / 3
+        };
+
+        is_even_step = !is_even_step;
+    }
+
+    bonus
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chain_length_bonus() {
+        assert_eq!(0, get_chain_length_bonus(4));
+        assert_eq!(1, get_chain_length_bonus(5));
+        assert_eq!(1, get_chain_length_bonus(6));
+        assert_eq!(2, get_chain_length_bonus(8));
+        assert_eq!(3, get_chain_length_bonus(12));
+        assert_eq!(4, get_chain_length_bonus(13));
+    }
+
+    #[test]
+    fn test_strategies_in_weight_order() {
+        // the solver takes the first strategy that finds a step, so a heavier strategy running
+        // ahead of a lighter one would inflate the rating
+        let weights: Vec<u32> = Strategy::get_all()
+            .iter()
+            .map(|strategy| strategy.get_weight())
+            .collect();
+
+        assert!(weights.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    fn test_rate() {
+        let bd =
+            "004005000010900340080002009705080020000203000090050801300500090076009010000300700";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let rating = rate(&grid).unwrap();
+
+        assert_eq!(Difficulty::Hard, rating.get_difficulty());
+        assert_eq!("Hard", rating.get_label());
+        assert!(rating.get_score() >= 3.2 && rating.get_score() < 6.0);
+    }
+
+    #[test]
+    fn test_rate_diabolical() {
+        let bd =
+            "004300000070019020000870005030000209052040830701000060200058000060730080000002700";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let (diabolical, easier): (Vec<Strategy>, Vec<Strategy>) = Strategy::get_all()
+            .into_iter()
+            .partition(|strategy| strategy.get_difficulty() == Difficulty::Diabolical);
+
+        // the puzzle can't be solved without a Diabolical step
+        let mut partial = grid.clone();
+
+        while let Some(result) = easier
+            .iter()
+            .find_map(|strategy| strategy.get_finder()(&partial))
+        {
+            for cell_candidate in result.get_to_place().iter() {
+                partial.place(cell_candidate);
+            }
+
+            for cell_candidate in result.get_to_eliminate().iter() {
+                partial.clear_candidate(cell_candidate);
+            }
+        }
+
+        assert!(!partial.is_complete());
+
+        // so its hardest step weighs at least as much as the easiest Diabolical strategy
+        let min_weight = diabolical
+            .iter()
+            .map(|strategy| strategy.get_weight())
+            .min()
+            .unwrap();
+
+        let rating = rate(&grid).unwrap();
+
+        assert_eq!(Difficulty::Diabolical, rating.get_difficulty());
+        assert!(rating.get_score() >= min_weight as f32 / 10.0);
+    }
+
+    #[test]
+    fn test_rate_unsolved() {
+        // no strategy can make progress on an empty grid
+        let grid = Grid::from_str(&"0".repeat(81)).unwrap();
+
+        assert_eq!(None, rate(&grid));
+    }
+}
@@ -14,9 +14,13 @@ impl Solver {
     }
 
     pub fn step(&self) -> Option<StrategyResult> {
+        self.step_with_strategy().map(|(_, result)| result)
+    }
+
+    pub fn step_with_strategy(&self) -> Option<(Strategy, StrategyResult)> {
         for strat in self.strategies.iter() {
             if let Some(result) = strat.get_finder()(&self.grid) {
-                return Some(result);
+                return Some((*strat, result));
             } else {
                 continue;
             }
@@ -7,7 +7,10 @@ use super::{
 };
 use crate::grid::{CellCandidate, Grid};
 
-use LinkType::{StrongInCell, StrongInUnit, WeakInCell, WeakInUnit};
+use LinkType::{
+    StrongGroupedInUnit, StrongInALS, StrongInCell, StrongInUnit, WeakGroupedInUnit, WeakInCell,
+    WeakInUnit, WeakToALS,
+};
 
 pub type AIC = Vec<LinkNode>;
 
@@ -28,7 +31,11 @@ pub struct AICResult {
 }
 
 impl AICResult {
-    pub fn from(aic: &AIC, aic_type: AICType, to_eliminate: Vec<CellCandidate>) -> AICResult {
+    pub fn from(aic: &AIC, aic_type: AICType, mut to_eliminate: Vec<CellCandidate>) -> AICResult {
+        // a candidate can be eliminated through both a group and its own node
+        to_eliminate.sort();
+        to_eliminate.dedup();
+
         AICResult {
             aic: aic.clone(),
             aic_type,
@@ -48,6 +55,10 @@ impl AICResult {
         &self.to_eliminate
     }
 
+    pub fn is_grouped(&self) -> bool {
+        self.aic.iter().any(|link_node| link_node.is_group())
+    }
+
     pub fn make_highlights(&self, alternate_color: bool, make_lines: bool) -> Vec<Highlight> {
         let mut highlights = vec![];
 
@@ -71,26 +82,28 @@ impl AICResult {
                 ));
             }
 
-            if (i % 2) == 0 {
-                highlights.push(Highlight::new_candidate_hl(
-                    current,
-                    if alternate_color {
-                        HighlightColor::NoteSecondaryFg
-                    } else {
-                        HighlightColor::NoteFg
-                    },
-                    if alternate_color {
-                        HighlightColor::NoteSecondaryBg
-                    } else {
-                        HighlightColor::NoteBg
-                    },
-                ));
-            } else {
-                highlights.push(Highlight::new_candidate_hl(
-                    current,
-                    HighlightColor::NoteFg,
-                    HighlightColor::NoteBg,
-                ));
+            for cell_candidate in aic[i].get().iter() {
+                if (i % 2) == 0 {
+                    highlights.push(Highlight::new_candidate_hl(
+                        cell_candidate,
+                        if alternate_color {
+                            HighlightColor::NoteSecondaryFg
+                        } else {
+                            HighlightColor::NoteFg
+                        },
+                        if alternate_color {
+                            HighlightColor::NoteSecondaryBg
+                        } else {
+                            HighlightColor::NoteBg
+                        },
+                    ));
+                } else {
+                    highlights.push(Highlight::new_candidate_hl(
+                        cell_candidate,
+                        HighlightColor::NoteFg,
+                        HighlightColor::NoteBg,
+                    ));
+                }
             }
         }
 
@@ -99,41 +112,98 @@ impl AICResult {
 }
 
 pub fn find_general_aic(grid: &Grid) -> Option<StrategyResult> {
-    let strong_link_map = make_link_map(grid, &[StrongInCell, StrongInUnit]);
-    let weak_link_map = make_link_map(grid, &[StrongInCell, StrongInUnit, WeakInCell, WeakInUnit]);
+    find_aic(grid, false).or_else(|| find_aic(grid, true))
+}
+
+// grouped nodes are only tried once there are no chains without them
+fn find_aic(grid: &Grid, grouped: bool) -> Option<StrategyResult> {
+    let mut strong_link_types = vec![StrongInCell, StrongInUnit];
+    let mut weak_link_types = vec![StrongInCell, StrongInUnit, WeakInCell, WeakInUnit];
+
+    if grouped {
+        strong_link_types.push(StrongGroupedInUnit);
+        weak_link_types.extend([StrongGroupedInUnit, WeakGroupedInUnit]);
+    }
+
+    let strong_link_map = make_link_map(grid, &strong_link_types);
+    let weak_link_map = make_link_map(grid, &weak_link_types);
 
     if let Some(aic_result) = build_aics(&strong_link_map, &weak_link_map, 12) {
         let highlights = make_highlights(&aic_result);
 
-        match aic_result.get_aic_type() {
-            AICType::Continuous => {
-                return Some(StrategyResult::from(
-                    "Continuous AIC loop",
-                    vec![],
-                    aic_result.get_to_eliminate().clone(),
-                    highlights,
-                ));
+        let name = match aic_result.get_aic_type() {
+            AICType::Continuous => "Continuous AIC loop",
+            AICType::Discontinuous(DiscontinuousType::Weak(_)) => "Discontinuous AIC Loop",
+            AICType::Discontinuous(DiscontinuousType::Strong(_)) => {
+                "Discontinuous AIC Loop (strong link)"
             }
+        };
 
-            AICType::Discontinuous(disc_type) => {
-                let name = match disc_type {
-                    DiscontinuousType::Weak(_) => "Discontinuous AIC Loop",
-                    DiscontinuousType::Strong(_) => "Discontinuous AIC Loop (strong link)",
-                };
-
-                return Some(StrategyResult::from(
-                    name,
-                    vec![],
-                    aic_result.get_to_eliminate().clone(),
-                    highlights,
-                ));
-            }
-        }
+        let name = if aic_result.is_grouped() {
+            format!("Grouped {}", name)
+        } else {
+            name.to_string()
+        };
+
+        return Some(StrategyResult::from_chain(
+            &name,
+            vec![],
+            aic_result.get_to_eliminate().clone(),
+            highlights,
+            aic_result.get_aic().len(),
+        ));
     }
 
     None
 }
 
+// Almost locked sets as nodes, for when there are no chains through single candidates or groups.
+// A strong link within a set isn't also a weak link, as the set can hold both candidates.
+pub fn find_aic_with_als(grid: &Grid) -> Option<StrategyResult> {
+    let strong_link_map = make_link_map(
+        grid,
+        &[StrongInCell, StrongInUnit, StrongGroupedInUnit, StrongInALS],
+    );
+    let mut weak_link_map = make_link_map(
+        grid,
+        &[
+            StrongInCell,
+            StrongInUnit,
+            StrongGroupedInUnit,
+            WeakInCell,
+            WeakInUnit,
+            WeakGroupedInUnit,
+            WeakToALS,
+        ],
+    );
+
+    // sets with no weak links can still be in the middle of a chain
+    for link_node in strong_link_map.keys() {
+        weak_link_map.entry(link_node.clone()).or_default();
+    }
+
+    // the sets add a lot of nodes, so the chains are kept shorter than for the other AICs
+    let aic_result = build_aics(&strong_link_map, &weak_link_map, 10)?;
+
+    let highlights = make_highlights(&aic_result);
+
+    let name = match aic_result.get_aic_type() {
+        AICType::Continuous => "AIC with ALS - Continuous Loop",
+        AICType::Discontinuous(DiscontinuousType::Weak(_)) => "AIC with ALS - Discontinuous Loop",
+        AICType::Discontinuous(DiscontinuousType::Strong(_)) => {
+            "AIC with ALS - Discontinuous Loop (strong link)"
+        }
+    };
+
+    Some(StrategyResult::from_chain(
+        name,
+        vec![],
+        aic_result.get_to_eliminate().clone(),
+        highlights,
+        aic_result.get_aic().len(),
+    ))
+}
+
 pub fn build_aics(
     strong_link_map: &LinkMap,
     weak_link_map: &LinkMap,
@@ -163,7 +233,11 @@ pub fn build_aics(
             }
 
             for node in search_map.get(current_node).unwrap().iter() {
-                if current_path.contains(node) {
+                // a group can't share a candidate with any other node of the chain
+                if current_path
+                    .iter()
+                    .any(|path_node| path_node.overlaps(node))
+                {
                     continue;
                 }
 
@@ -296,7 +370,7 @@ fn check_discontinuous(
     }
 }
 
-fn make_highlights(aic_result: &AICResult) -> Vec<Highlight> {
+pub fn make_highlights(aic_result: &AICResult) -> Vec<Highlight> {
     let mut highlights = aic_result.make_highlights(true, true);
 
     for cell_candidate in aic_result.get_to_eliminate().iter() {
@@ -501,4 +575,33 @@ mod tests {
 
         // panic!();
     }
+
+    #[test]
+    fn test_grouped_aic() {
+        // only has chains through grouped nodes
+        let bd =
+            "000000012000000003002300400001800005060070800000009000008500000900040500470006000";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let aic = find_general_aic(&grid).unwrap();
+
+        assert!(aic.get_name().starts_with("Grouped"));
+        assert!(aic
+            .get_to_eliminate()
+            .contains(&CellCandidate::from(4, 2, 5)));
+    }
+
+    #[test]
+    fn test_aic_with_als() {
+        // no chains without almost locked sets
+        let bd =
+            "097534820312786954054019003768145392529673148143928670235897400471362000986451237";
+        let grid = Grid::from_str(bd).unwrap();
+
+        assert!(find_general_aic(&grid).is_none());
+
+        let aic = find_aic_with_als(&grid).unwrap();
+
+        assert_eq!(vec![CellCandidate::from(7, 8, 5)], *aic.get_to_eliminate());
+    }
 }
@@ -0,0 +1,200 @@
+use itertools::Itertools;
+
+use super::{
+    highlight::{Highlight, HighlightColor},
+    StrategyResult,
+};
+use crate::grid::{Cell, CellCandidate, Grid};
+use crate::util::BitSet;
+
+pub fn find_aligned_pair_exclusion(grid: &Grid) -> Option<StrategyResult> {
+    find_aligned_exclusion(grid, 2)
+}
+
+pub fn find_aligned_triple_exclusion(grid: &Grid) -> Option<StrategyResult> {
+    find_aligned_exclusion(grid, 3)
+}
+
+// Aligned exclusion looks at every combination of values for a few cells that see each other. A
+// combination is impossible if two cells that see each other share a value, or if it would take
+// both candidates of a bivalue cell seeing them. A candidate of a cell that isn't in any possible
+// combination is false.
+fn find_aligned_exclusion(grid: &Grid, size: usize) -> Option<StrategyResult> {
+    // sorted so that the patterns are always found in the same order
+    let mut cells: Vec<Cell> = grid
+        .as_region()
+        .iter()
+        .filter(|cell| cell.get_candidates().len() >= 2)
+        .cloned()
+        .collect();
+    cells.sort_by_key(|cell| (cell.get_row(), cell.get_col()));
+
+    let mut bivalue_cells: Vec<Cell> = grid.get_nvalue_cells(2).iter().cloned().collect();
+    bivalue_cells.sort_by_key(|cell| (cell.get_row(), cell.get_col()));
+
+    for base in cells.iter().cloned().combinations(size) {
+        if !is_aligned(&base) {
+            continue;
+        }
+
+        let sources: Vec<&Cell> = bivalue_cells
+            .iter()
+            .filter(|source| !base.contains(source))
+            .filter(|source| base.iter().filter(|cell| cell.can_see(source)).count() >= 2)
+            .collect();
+
+        if sources.is_empty() {
+            continue;
+        }
+
+        let mut possible = vec![BitSet::new(); size];
+
+        for combination in base
+            .iter()
+            .map(|cell| cell.get_candidates().iter().collect::<Vec<u32>>())
+            .multi_cartesian_product()
+        {
+            if is_excluded(&base, &combination, &sources) {
+                continue;
+            }
+
+            for (vals, val) in possible.iter_mut().zip(combination.iter()) {
+                vals.insert(*val);
+            }
+        }
+
+        let to_eliminate: Vec<CellCandidate> = base
+            .iter()
+            .zip(possible.iter())
+            .flat_map(|(cell, vals)| {
+                cell.get_candidates()
+                    .difference(vals)
+                    .iter()
+                    .map(|val| CellCandidate::from_cell(cell, val))
+                    .collect::<Vec<CellCandidate>>()
+            })
+            .collect();
+
+        if to_eliminate.is_empty() {
+            continue;
+        }
+
+        let highlights = make_highlights(&base, &sources, &to_eliminate);
+
+        let name = match size {
+            2 => "Aligned Pair Exclusion",
+            3 => "Aligned Triple Exclusion",
+            _ => unreachable!(),
+        };
+
+        return Some(StrategyResult::from(name, vec![], to_eliminate, highlights));
+    }
+
+    None
+}
+
+// a pair has to see each other, and in a triple at least one cell has to see the other two
+fn is_aligned(base: &[Cell]) -> bool {
+    base.iter().any(|cell| {
+        base.iter()
+            .filter(|other| *other != cell)
+            .all(|other| cell.can_see(other))
+    })
+}
+
+fn is_excluded(base: &[Cell], combination: &[u32], sources: &[&Cell]) -> bool {
+    let sees_same_value = (0..base.len())
+        .tuple_combinations()
+        .any(|(i, j)| combination[i] == combination[j] && base[i].can_see(&base[j]));
+
+    if sees_same_value {
+        return true;
+    }
+
+    sources.iter().any(|source| {
+        let mut taken = BitSet::new();
+
+        for (cell, val) in base.iter().zip(combination.iter()) {
+            if cell.can_see(source) {
+                taken.insert(*val);
+            }
+        }
+
+        source.get_candidates().difference(&taken).is_empty()
+    })
+}
+
+fn make_highlights(
+    base: &[Cell],
+    sources: &[&Cell],
+    to_eliminate: &[CellCandidate],
+) -> Vec<Highlight> {
+    let mut highlights = vec![];
+
+    for cell in base.iter() {
+        highlights.push(Highlight::new_cell_hl(
+            cell.get_row(),
+            cell.get_col(),
+            HighlightColor::Yellow,
+        ));
+    }
+
+    for source in sources.iter() {
+        for val in source.get_candidates().iter() {
+            highlights.push(Highlight::new_candidate_hl(
+                &CellCandidate::from_cell(source, val),
+                HighlightColor::NoteSecondaryFg,
+                HighlightColor::NoteSecondaryBg,
+            ));
+        }
+    }
+
+    for cell_candidate in to_eliminate.iter() {
+        highlights.push(Highlight::new_candidate_hl(
+            cell_candidate,
+            HighlightColor::ElimFg,
+            HighlightColor::ElimBg,
+        ));
+    }
+
+    highlights
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aligned_pair_exclusion() {
+        let bd =
+            "004005000010900340080002009705080020000203000090050801300500090076009010000300700";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let expected = vec![CellCandidate::from(6, 2, 2)];
+
+        let aligned_pair_exclusion = find_aligned_pair_exclusion(&grid).unwrap();
+        let to_place = aligned_pair_exclusion.get_to_place().clone();
+        let to_eliminate = aligned_pair_exclusion.get_to_eliminate().clone();
+
+        assert_eq!(Vec::<CellCandidate>::new(), to_place);
+        assert_eq!(expected, to_eliminate);
+    }
+
+    #[test]
+    fn test_aligned_triple_exclusion() {
+        let bd =
+            "302109508000803000000627000007406200400001009000985000030000050078004120009502800";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let expected = vec![CellCandidate::from(1, 0, 1), CellCandidate::from(1, 0, 6)];
+
+        assert!(find_aligned_pair_exclusion(&grid).is_none());
+
+        let aligned_triple_exclusion = find_aligned_triple_exclusion(&grid).unwrap();
+        let mut to_eliminate = aligned_triple_exclusion.get_to_eliminate().clone();
+
+        to_eliminate.sort();
+
+        assert_eq!(expected, to_eliminate);
+    }
+}
@@ -0,0 +1,578 @@
+use itertools::Itertools;
+
+use std::collections::{BTreeSet, HashSet};
+
+use super::{
+    highlight::{Highlight, HighlightColor},
+    StrategyResult,
+};
+use crate::grid::{Cell, CellCandidate, Grid, UnitType};
+use crate::util::BitSet;
+
+use UnitType::{Col, MiniGrid, Row};
+
+// Almost Locked Set: n cells in a unit with n + 1 candidates between them. Removing any one of the
+// candidates would leave a locked (naked) set.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ALS {
+    cells: Vec<Cell>,
+    candidates: BitSet,
+}
+
+impl ALS {
+    fn from(cells: Vec<Cell>) -> ALS {
+        let mut candidates = BitSet::new();
+
+        for cell in cells.iter() {
+            candidates.extend(cell.get_candidates());
+        }
+
+        ALS { cells, candidates }
+    }
+
+    pub fn get_cells(&self) -> &Vec<Cell> {
+        &self.cells
+    }
+
+    pub fn get_candidates(&self) -> &BitSet {
+        &self.candidates
+    }
+
+    pub fn get_cells_with(&self, val: u32) -> Vec<&Cell> {
+        self.cells
+            .iter()
+            .filter(|cell| cell.get_candidates().contains(val))
+            .collect()
+    }
+
+    pub fn get_cell_candidates(&self, val: u32) -> Vec<CellCandidate> {
+        self.get_cells_with(val)
+            .into_iter()
+            .map(|cell| CellCandidate::from_cell(cell, val))
+            .collect()
+    }
+
+    pub fn contains_cell(&self, row: u32, col: u32) -> bool {
+        self.cells
+            .iter()
+            .any(|cell| cell.get_row() == row && cell.get_col() == col)
+    }
+
+    pub fn overlaps(&self, other: &ALS) -> bool {
+        self.cells
+            .iter()
+            .any(|cell| other.contains_cell(cell.get_row(), cell.get_col()))
+    }
+
+    // val is a restricted common candidate of the two sets if every cell of one that could be val
+    // sees every cell of the other that could be val, so that at most one of the sets holds val
+    pub fn is_restricted_common(&self, other: &ALS, val: u32) -> bool {
+        if !self.candidates.contains(val) || !other.candidates.contains(val) {
+            return false;
+        }
+
+        let other_cells = other.get_cells_with(val);
+
+        self.get_cells_with(val).iter().all(|cell| {
+            other_cells
+                .iter()
+                .all(|other_cell| cell.can_see(other_cell))
+        })
+    }
+
+    pub fn get_restricted_commons(&self, other: &ALS) -> BitSet {
+        let mut restricted_commons = BitSet::new();
+
+        if self.overlaps(other) {
+            return restricted_commons;
+        }
+
+        for val in self.candidates.intersection(&other.candidates).iter() {
+            if self.is_restricted_common(other, val) {
+                restricted_commons.insert(val);
+            }
+        }
+
+        restricted_commons
+    }
+}
+
+// All the almost locked sets in the grid with at most max_size cells. A set of cells that lies in
+// two units (e.g. a row and a minigrid) is only returned once.
+pub fn get_all_als(grid: &Grid, max_size: usize) -> Vec<ALS> {
+    let mut all_als = vec![];
+    let mut seen = HashSet::new();
+
+    for unit_type in &[Row, Col, MiniGrid] {
+        for k in 0..9 {
+            // sorted so that the sets are always found in the same order
+            let mut cells: Vec<Cell> = grid.get_unit(unit_type, k).iter().cloned().collect();
+            cells.sort_by_key(|cell| (cell.get_row(), cell.get_col()));
+
+            for size in 1..=max_size.min(cells.len()) {
+                for combination in cells.iter().cloned().combinations(size) {
+                    let mut candidates = BitSet::new();
+
+                    for cell in combination.iter() {
+                        candidates.extend(cell.get_candidates());
+                    }
+
+                    if candidates.len() != size as u32 + 1 {
+                        continue;
+                    }
+
+                    let mut key: Vec<(u32, u32)> = combination
+                        .iter()
+                        .map(|cell| (cell.get_row(), cell.get_col()))
+                        .collect();
+                    key.sort();
+
+                    if seen.insert(key) {
+                        all_als.push(ALS::from(combination));
+                    }
+                }
+            }
+        }
+    }
+
+    all_als
+}
+
+pub fn find_als_xz(grid: &Grid) -> Option<StrategyResult> {
+    let all_als = get_all_als(grid, 8);
+
+    for (a, b) in all_als.iter().tuple_combinations::<(_, _)>() {
+        let restricted_commons = a.get_restricted_commons(b);
+
+        if restricted_commons.is_empty() {
+            continue;
+        }
+
+        // sorted so that the eliminations are always reported in the same order
+        let mut to_eliminate = BTreeSet::new();
+
+        // one of a and b has to be locked without x, so a common candidate z must be in one of them
+        for z in a.get_candidates().intersection(b.get_candidates()).iter() {
+            if restricted_commons.contains(z) {
+                continue;
+            }
+
+            let mut z_cells = a.get_cell_candidates(z);
+            z_cells.extend(b.get_cell_candidates(z));
+
+            to_eliminate.extend(get_seen_by_all(grid, &z_cells, &[a, b]));
+        }
+
+        // with two restricted commons both sets are locked, one holding x and the other holding y
+        let is_doubly_linked = restricted_commons.len() > 1;
+
+        if is_doubly_linked {
+            for x in restricted_commons.iter() {
+                let mut x_cells = a.get_cell_candidates(x);
+                x_cells.extend(b.get_cell_candidates(x));
+
+                to_eliminate.extend(get_seen_by_all(grid, &x_cells, &[a, b]));
+            }
+
+            for als in [a, b] {
+                for val in als.get_candidates().difference(&restricted_commons).iter() {
+                    let val_cells = als.get_cell_candidates(val);
+
+                    to_eliminate.extend(get_seen_by_all(grid, &val_cells, &[a, b]));
+                }
+            }
+        }
+
+        if to_eliminate.is_empty() {
+            continue;
+        }
+
+        let to_eliminate: Vec<CellCandidate> = to_eliminate.into_iter().collect();
+
+        let highlights = make_highlights(&[a, b], &restricted_commons, &to_eliminate);
+
+        let name = if is_doubly_linked {
+            "ALS-XZ (doubly linked)"
+        } else {
+            "ALS-XZ"
+        };
+
+        return Some(StrategyResult::from(name, vec![], to_eliminate, highlights));
+    }
+
+    None
+}
+
+pub fn find_als_xy_wing(grid: &Grid) -> Option<StrategyResult> {
+    let all_als = get_all_als(grid, 8);
+
+    // restricted commons between every pair of sets that have any
+    let mut linked = vec![vec![]; all_als.len()];
+
+    for (i, j) in (0..all_als.len()).tuple_combinations::<(_, _)>() {
+        let restricted_commons = all_als[i].get_restricted_commons(&all_als[j]);
+
+        if !restricted_commons.is_empty() {
+            linked[i].push((j, restricted_commons.clone()));
+            linked[j].push((i, restricted_commons));
+        }
+    }
+
+    for (c_idx, pivot) in all_als.iter().enumerate() {
+        for ((a_idx, a_rccs), (b_idx, b_rccs)) in linked[c_idx].iter().tuple_combinations() {
+            let a = &all_als[*a_idx];
+            let b = &all_als[*b_idx];
+
+            if a.overlaps(b) {
+                continue;
+            }
+
+            for (x, y) in a_rccs
+                .iter()
+                .cartesian_product(b_rccs.iter().collect::<Vec<u32>>())
+            {
+                if x == y {
+                    continue;
+                }
+
+                let mut to_eliminate = BTreeSet::new();
+
+                // if a isn't locked without x then the pivot is locked without x, so it must be
+                // locked without y and b has to be locked without y. So z has to be in a or b
+                for z in a.get_candidates().intersection(b.get_candidates()).iter() {
+                    if z == x || z == y {
+                        continue;
+                    }
+
+                    let mut z_cells = a.get_cell_candidates(z);
+                    z_cells.extend(b.get_cell_candidates(z));
+
+                    to_eliminate.extend(get_seen_by_all(grid, &z_cells, &[a, b, pivot]));
+                }
+
+                if to_eliminate.is_empty() {
+                    continue;
+                }
+
+                let to_eliminate: Vec<CellCandidate> = to_eliminate.into_iter().collect();
+
+                let highlights =
+                    make_highlights(&[a, b, pivot], &BitSet::from(&[x, y]), &to_eliminate);
+
+                return Some(StrategyResult::from(
+                    "ALS-XY-Wing",
+                    vec![],
+                    to_eliminate,
+                    highlights,
+                ));
+            }
+        }
+    }
+
+    None
+}
+
+// A stem cell with up to this many candidates, one petal for each
+const MAX_PETALS: u32 = 3;
+
+// Death Blossom: a stem cell where, for each of its candidates x, there is an ALS (petal) whose
+// cells that could be x all see the stem. Whichever candidate the stem takes, the petal for it is
+// locked without x. So if every petal has a candidate z, z is in one of them, and cells seeing all
+// of the petals' z can't be z.
+pub fn find_death_blossom(grid: &Grid) -> Option<StrategyResult> {
+    let all_als = get_all_als(grid, 8);
+
+    // sorted so that the patterns are always found in the same order
+    let mut stems: Vec<Cell> = grid
+        .as_region()
+        .iter()
+        .filter(|cell| (2..=MAX_PETALS).contains(&cell.get_candidates().len()))
+        .cloned()
+        .collect();
+    stems.sort_by_key(|cell| (cell.get_row(), cell.get_col()));
+
+    for stem in stems.iter() {
+        let petal_options: Vec<(u32, Vec<&ALS>)> = stem
+            .get_candidates()
+            .iter()
+            .map(|x| {
+                let petals = all_als
+                    .iter()
+                    .filter(|als| is_petal(als, stem, x))
+                    .collect();
+
+                (x, petals)
+            })
+            .collect();
+
+        for z in 1..10 {
+            if stem.get_candidates().contains(z) {
+                continue;
+            }
+
+            let options: Vec<(u32, Vec<&ALS>)> = petal_options
+                .iter()
+                .map(|(x, petals)| {
+                    let petals = petals
+                        .iter()
+                        .filter(|als| als.get_candidates().contains(z))
+                        .cloned()
+                        .collect();
+
+                    (*x, petals)
+                })
+                .collect();
+
+            if options.iter().any(|(_, petals)| petals.is_empty()) {
+                continue;
+            }
+
+            if let Some(res) = choose_petals(grid, stem, z, &options, &mut vec![]) {
+                return Some(res);
+            }
+        }
+    }
+
+    None
+}
+
+fn is_petal(als: &ALS, stem: &Cell, x: u32) -> bool {
+    !als.contains_cell(stem.get_row(), stem.get_col())
+        && als.get_candidates().contains(x)
+        && als.get_cells_with(x).iter().all(|cell| cell.can_see(stem))
+}
+
+// Picks non-overlapping petals one stem candidate at a time, giving up on a branch as soon as no
+// cell sees all of the chosen petals' z
+fn choose_petals<'a>(
+    grid: &Grid,
+    stem: &Cell,
+    z: u32,
+    options: &[(u32, Vec<&'a ALS>)],
+    petals: &mut Vec<&'a ALS>,
+) -> Option<StrategyResult> {
+    let z_cells: Vec<CellCandidate> = petals
+        .iter()
+        .flat_map(|petal| petal.get_cell_candidates(z))
+        .collect();
+
+    let to_eliminate = get_seen_by_all(grid, &z_cells, petals);
+
+    if !petals.is_empty() && to_eliminate.is_empty() {
+        return None;
+    }
+
+    if petals.len() == options.len() {
+        let highlights = make_death_blossom_highlights(stem, options, petals, &to_eliminate);
+
+        return Some(StrategyResult::from(
+            "Death Blossom",
+            vec![],
+            to_eliminate,
+            highlights,
+        ));
+    }
+
+    for petal in options[petals.len()].1.iter() {
+        if petals.iter().any(|other| other.overlaps(petal)) {
+            continue;
+        }
+
+        petals.push(petal);
+
+        if let Some(res) = choose_petals(grid, stem, z, options, petals) {
+            return Some(res);
+        }
+
+        petals.pop();
+    }
+
+    None
+}
+
+fn make_death_blossom_highlights(
+    stem: &Cell,
+    options: &[(u32, Vec<&ALS>)],
+    petals: &[&ALS],
+    to_eliminate: &[CellCandidate],
+) -> Vec<Highlight> {
+    let mut highlights = make_highlights(petals, stem.get_candidates(), to_eliminate);
+
+    highlights.push(Highlight::new_cell_hl(
+        stem.get_row(),
+        stem.get_col(),
+        HighlightColor::Magenta,
+    ));
+
+    for ((x, _), petal) in options.iter().zip(petals.iter()) {
+        let stem_candidate = CellCandidate::from_cell(stem, *x);
+
+        highlights.push(Highlight::new_candidate_hl(
+            &stem_candidate,
+            HighlightColor::NoteFg,
+            HighlightColor::NoteBg,
+        ));
+
+        for petal_candidate in petal.get_cell_candidates(*x).iter() {
+            highlights.push(Highlight::new_line_hl(
+                &stem_candidate,
+                petal_candidate,
+                HighlightColor::Red,
+                true,
+            ));
+        }
+    }
+
+    highlights
+}
+
+// candidates of the same value as the given cell candidates, outside of the given sets, which
+// see all of them
+pub fn get_seen_by_all(
+    grid: &Grid,
+    cell_candidates: &[CellCandidate],
+    exclude: &[&ALS],
+) -> Vec<CellCandidate> {
+    let mut seen_by_all = vec![];
+
+    let Some(val) = cell_candidates.first().map(|cc| cc.get_val()) else {
+        return seen_by_all;
+    };
+
+    for cell in grid.as_region().scan(val).iter() {
+        if exclude
+            .iter()
+            .any(|als| als.contains_cell(cell.get_row(), cell.get_col()))
+        {
+            continue;
+        }
+
+        let target = CellCandidate::from_cell(cell, val);
+
+        if cell_candidates.iter().all(|cc| target.can_see(cc, true)) {
+            seen_by_all.push(target);
+        }
+    }
+
+    seen_by_all
+}
+
+fn make_highlights(
+    all_als: &[&ALS],
+    linking: &BitSet,
+    to_eliminate: &[CellCandidate],
+) -> Vec<Highlight> {
+    let colors = [
+        HighlightColor::Cyan,
+        HighlightColor::Yellow,
+        HighlightColor::Orange,
+    ];
+
+    let mut highlights = vec![];
+
+    for (als, color) in all_als.iter().zip(colors.iter()) {
+        for cell in als.get_cells().iter() {
+            highlights.push(Highlight::new_cell_hl(
+                cell.get_row(),
+                cell.get_col(),
+                *color,
+            ));
+
+            for val in cell.get_candidates().iter() {
+                let (fg, bg) = if linking.contains(val) {
+                    (HighlightColor::NoteFg, HighlightColor::NoteBg)
+                } else {
+                    (
+                        HighlightColor::NoteSecondaryFg,
+                        HighlightColor::NoteSecondaryBg,
+                    )
+                };
+
+                highlights.push(Highlight::new_candidate_hl(
+                    &CellCandidate::from_cell(cell, val),
+                    fg,
+                    bg,
+                ));
+            }
+        }
+    }
+
+    for cell_candidate in to_eliminate.iter() {
+        highlights.push(Highlight::new_candidate_hl(
+            cell_candidate,
+            HighlightColor::ElimFg,
+            HighlightColor::ElimBg,
+        ));
+    }
+
+    highlights
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_als_xz() {
+        let bd =
+            "004005000010900340080002009705080020000203000090050801300500090076009010000300700";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let expected = vec![CellCandidate::from(0, 0, 2)];
+
+        let als_xz = find_als_xz(&grid).unwrap();
+        let to_eliminate = als_xz.get_to_eliminate().clone();
+
+        assert_eq!("ALS-XZ", als_xz.get_name());
+        assert_eq!(expected, to_eliminate);
+    }
+
+    #[test]
+    fn test_als_xz_doubly_linked() {
+        let bd =
+            "300000020064500000000901604010007300700000001005800090501603000000002810080000002";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let expected = vec![
+            CellCandidate::from(0, 6, 5),
+            CellCandidate::from(0, 6, 7),
+            CellCandidate::from(0, 6, 9),
+        ];
+
+        let als_xz = find_als_xz(&grid).unwrap();
+        let to_eliminate = als_xz.get_to_eliminate().clone();
+
+        assert_eq!("ALS-XZ (doubly linked)", als_xz.get_name());
+        assert_eq!(expected, to_eliminate);
+    }
+
+    #[test]
+    fn test_als_xy_wing() {
+        let bd =
+            "300000020064500000000901604010007300700000001005800090501603000000002810080000002";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let expected = vec![CellCandidate::from(0, 4, 8)];
+
+        let als_xy_wing = find_als_xy_wing(&grid).unwrap();
+        let to_eliminate = als_xy_wing.get_to_eliminate().clone();
+
+        assert_eq!("ALS-XY-Wing", als_xy_wing.get_name());
+        assert_eq!(expected, to_eliminate);
+    }
+
+    #[test]
+    fn test_death_blossom() {
+        let bd =
+            "001003007000041200006700000800000402027000800000500001000957000700030005000000608";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let expected = vec![CellCandidate::from(8, 7, 1)];
+
+        let death_blossom = find_death_blossom(&grid).unwrap();
+        let to_eliminate = death_blossom.get_to_eliminate().clone();
+
+        assert_eq!("Death Blossom", death_blossom.get_name());
+        assert_eq!(expected, to_eliminate);
+    }
+}
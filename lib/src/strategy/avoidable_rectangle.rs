@@ -0,0 +1,297 @@
+use itertools::Itertools;
+
+use super::{
+    highlight::{Highlight, HighlightColor},
+    StrategyResult,
+};
+use crate::grid::{Cell, CellCandidate, Grid};
+
+// Like a unique rectangle, but using cells that were solved rather than given. If the four corners
+// of a rectangle in two minigrids ended up as a b / b a and none of them are givens, a and b could
+// be swapped to give a second solution. Assuming the puzzle is unique, the solved corners force
+// the remaining ones away from that pattern.
+struct Rectangle {
+    // ordered so that corners i and 3 - i are diagonal
+    coords: [(u32, u32); 4],
+}
+
+impl Rectangle {
+    fn placed(&self, grid: &Grid, i: usize) -> u32 {
+        let (row, col) = self.coords[i];
+        grid.get_placed(row, col)
+    }
+
+    fn cell(&self, grid: &Grid, i: usize) -> Cell {
+        let (row, col) = self.coords[i];
+        Cell::from(row, col, grid.get_candidates(row, col))
+    }
+}
+
+pub fn find_avoidable_rectangle(grid: &Grid) -> Option<StrategyResult> {
+    let rectangles = get_rectangles(grid);
+
+    let finders = [type_1, type_2];
+
+    for finder in finders.iter() {
+        for rect in rectangles.iter() {
+            if let Some(res) = finder(grid, rect) {
+                return Some(res);
+            }
+        }
+    }
+
+    None
+}
+
+fn get_rectangles(grid: &Grid) -> Vec<Rectangle> {
+    let mut rectangles = vec![];
+
+    for (r1, r2) in (0..9).tuple_combinations::<(_, _)>() {
+        for (c1, c2) in (0..9).tuple_combinations::<(_, _)>() {
+            // the rectangle has to span exactly two minigrids
+            if (r1 / 3 == r2 / 3) == (c1 / 3 == c2 / 3) {
+                continue;
+            }
+
+            let coords = [(r1, c1), (r1, c2), (r2, c1), (r2, c2)];
+
+            if coords.iter().any(|&(r, c)| grid.is_given(r, c)) {
+                continue;
+            }
+
+            rectangles.push(Rectangle { coords });
+        }
+    }
+
+    rectangles
+}
+
+// three corners are solved as a, b and b with a diagonal to the unsolved corner, which then can't
+// be a
+fn type_1(grid: &Grid, rect: &Rectangle) -> Option<StrategyResult> {
+    let unsolved: Vec<usize> = (0..4).filter(|&i| rect.placed(grid, i) == 0).collect();
+
+    if unsolved.len() != 1 {
+        return None;
+    }
+
+    let i = unsolved[0];
+    let a = rect.placed(grid, 3 - i);
+    let b = rect.placed(grid, i ^ 1);
+
+    if a == b || rect.placed(grid, i ^ 2) != b {
+        return None;
+    }
+
+    let cell = rect.cell(grid, i);
+
+    if !cell.get_candidates().contains(a) {
+        return None;
+    }
+
+    let to_eliminate = vec![CellCandidate::from_cell(&cell, a)];
+
+    let highlights = make_highlights(grid, rect, &[a, b], &to_eliminate);
+
+    Some(StrategyResult::from(
+        "Avoidable Rectangle Type 1",
+        vec![],
+        to_eliminate,
+        highlights,
+    ))
+}
+
+// two corners in a line are solved as a and b, and the other two are bx opposite a and ax
+// opposite b. They can't be b and a, so one of them must be x.
+fn type_2(grid: &Grid, rect: &Rectangle) -> Option<StrategyResult> {
+    let unsolved: Vec<usize> = (0..4).filter(|&i| rect.placed(grid, i) == 0).collect();
+
+    if unsolved.len() != 2 || unsolved[0] + unsolved[1] == 3 {
+        return None;
+    }
+
+    // the corner opposite to corner i along the other line
+    let opposite = |i: usize| {
+        if unsolved[1] - unsolved[0] == 1 {
+            i ^ 2
+        } else {
+            i ^ 1
+        }
+    };
+
+    let roof: Vec<Cell> = unsolved.iter().map(|&i| rect.cell(grid, i)).collect();
+    let floor: Vec<u32> = unsolved
+        .iter()
+        .map(|&i| rect.placed(grid, opposite(i)))
+        .collect();
+
+    // each unsolved corner needs the value solved diagonally from it, which is the one solved
+    // opposite to the other unsolved corner
+    let deadly = [floor[1], floor[0]];
+
+    let extras: Vec<_> = roof
+        .iter()
+        .zip(deadly.iter())
+        .map(|(cell, &val)| {
+            if !cell.get_candidates().contains(val) {
+                return None;
+            }
+
+            let mut extras = cell.get_candidates().clone();
+            extras.remove(val);
+
+            Some(extras)
+        })
+        .collect();
+
+    let (Some(extras), Some(other_extras)) = (&extras[0], &extras[1]) else {
+        return None;
+    };
+
+    if extras.len() != 1 || extras != other_extras {
+        return None;
+    }
+
+    let x = extras.get_smallest();
+
+    let to_eliminate: Vec<CellCandidate> = grid
+        .get_cells_that_see(&roof[0], false)
+        .intersection(&grid.get_cells_that_see(&roof[1], false))
+        .scan(x)
+        .iter()
+        .map(|cell| CellCandidate::from_cell(cell, x))
+        .collect();
+
+    if to_eliminate.is_empty() {
+        return None;
+    }
+
+    let highlights = make_highlights(grid, rect, &deadly, &to_eliminate);
+
+    Some(StrategyResult::from(
+        "Avoidable Rectangle Type 2",
+        vec![],
+        to_eliminate,
+        highlights,
+    ))
+}
+
+fn make_highlights(
+    grid: &Grid,
+    rect: &Rectangle,
+    deadly: &[u32],
+    to_eliminate: &[CellCandidate],
+) -> Vec<Highlight> {
+    let mut highlights = vec![];
+
+    for i in 0..4 {
+        let cell = rect.cell(grid, i);
+
+        highlights.push(Highlight::new_cell_hl(
+            cell.get_row(),
+            cell.get_col(),
+            HighlightColor::Orange,
+        ));
+
+        for &val in deadly.iter() {
+            if cell.get_candidates().contains(val) {
+                highlights.push(Highlight::new_candidate_hl(
+                    &CellCandidate::from_cell(&cell, val),
+                    HighlightColor::NoteFg,
+                    HighlightColor::NoteBg,
+                ));
+            }
+        }
+    }
+
+    for cell_candidate in to_eliminate.iter() {
+        highlights.push(Highlight::new_candidate_hl(
+            cell_candidate,
+            HighlightColor::ElimFg,
+            HighlightColor::ElimBg,
+        ));
+    }
+
+    highlights
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solve_cells(bd: &str, solved: &[(u32, u32, u32)]) -> Grid {
+        let mut grid = Grid::from_str(bd).unwrap();
+
+        for &(row, col, val) in solved.iter() {
+            grid.place(&CellCandidate::from(row, col, val));
+        }
+
+        grid
+    }
+
+    #[test]
+    fn test_avoidable_rectangle_type_1() {
+        let bd =
+            "005064000703000006000700480200970500008510000000000003002800065960000004000000090";
+        let solved = [
+            (1, 1, 4),
+            (1, 7, 5),
+            (2, 4, 3),
+            (2, 5, 5),
+            (3, 8, 8),
+            (7, 4, 5),
+            (7, 6, 8),
+        ];
+        let grid = solve_cells(bd, &solved);
+
+        let expected = vec![CellCandidate::from(7, 5, 3)];
+
+        let avoidable_rectangle = find_avoidable_rectangle(&grid).unwrap();
+        let to_eliminate = avoidable_rectangle.get_to_eliminate().clone();
+
+        assert_eq!("Avoidable Rectangle Type 1", avoidable_rectangle.get_name());
+        assert_eq!(expected, to_eliminate);
+
+        // the same cells as givens don't form an avoidable rectangle
+        let given = Grid::from_str(&grid.to_bd_str()).unwrap();
+
+        assert!(find_avoidable_rectangle(&given).is_none());
+    }
+
+    #[test]
+    fn test_avoidable_rectangle_type_2() {
+        let bd =
+            "000100960200000000005008040710020000400000050000000283070006029000070000900043800";
+        let solved = [
+            (0, 5, 2),
+            (1, 5, 4),
+            (2, 3, 7),
+            (2, 8, 2),
+            (3, 5, 5),
+            (3, 6, 6),
+            (3, 7, 9),
+            (3, 8, 4),
+            (4, 5, 9),
+            (5, 0, 5),
+            (5, 3, 4),
+            (5, 4, 1),
+            (5, 5, 7),
+            (7, 3, 9),
+            (7, 5, 1),
+            (7, 7, 3),
+            (8, 1, 5),
+            (8, 3, 2),
+        ];
+        let grid = solve_cells(bd, &solved);
+
+        let expected = vec![CellCandidate::from(6, 6, 5)];
+
+        let avoidable_rectangle = find_avoidable_rectangle(&grid).unwrap();
+        let mut to_eliminate = avoidable_rectangle.get_to_eliminate().clone();
+
+        to_eliminate.sort();
+
+        assert_eq!("Avoidable Rectangle Type 2", avoidable_rectangle.get_name());
+        assert_eq!(expected, to_eliminate);
+    }
+}
@@ -0,0 +1,203 @@
+use std::collections::HashSet;
+
+use super::{
+    highlight::{Highlight, HighlightColor},
+    StrategyResult,
+};
+use crate::grid::{CellCandidate, Grid, UnitType};
+
+use UnitType::{Col, MiniGrid, Row};
+
+// Bivalue Universal Grave: if every unsolved cell had exactly two candidates and every candidate
+// appeared exactly twice in each of its units, the puzzle would have two solutions. So, assuming
+// the puzzle is unique, at least one of the candidates that stop the grid from being in that state
+// must be true.
+pub fn find_bug(grid: &Grid) -> Option<StrategyResult> {
+    let bug_candidates = get_bug_candidates(grid)?;
+
+    if bug_candidates.len() == 1 {
+        let highlights = make_highlights(&bug_candidates, &[]);
+
+        return Some(StrategyResult::from(
+            "BUG+1",
+            bug_candidates,
+            vec![],
+            highlights,
+        ));
+    }
+
+    // anything that would remove all of the bug candidates can be eliminated
+    let mut to_eliminate = vec![];
+
+    for cell in grid.as_region().iter() {
+        for val in cell.get_candidates().iter() {
+            let target = CellCandidate::from_cell(cell, val);
+
+            if bug_candidates.contains(&target) {
+                continue;
+            }
+
+            if bug_candidates.iter().all(|bug| removes(&target, bug)) {
+                to_eliminate.push(target);
+            }
+        }
+    }
+
+    if to_eliminate.is_empty() {
+        return None;
+    }
+
+    let highlights = make_highlights(&bug_candidates, &to_eliminate);
+
+    Some(StrategyResult::from(
+        &format!("BUG+{}", bug_candidates.len()),
+        vec![],
+        to_eliminate,
+        highlights,
+    ))
+}
+
+// The candidates which have to be removed to leave the grid in the bug state, or None if the grid
+// can't be reduced to one.
+fn get_bug_candidates(grid: &Grid) -> Option<Vec<CellCandidate>> {
+    let cells = grid.as_region();
+
+    if cells.iter().any(|cell| cell.get_candidates().len() < 2) {
+        return None;
+    }
+
+    let mut bug_candidates = vec![];
+
+    for cell in cells.iter() {
+        if cell.get_candidates().len() == 2 {
+            continue;
+        }
+
+        // an extra candidate shows up more than twice in each of the cell's units
+        for val in cell.get_candidates().iter() {
+            let is_extra = [Row, Col, MiniGrid]
+                .iter()
+                .all(|unit_type| grid.get_unit_containing(unit_type, cell).scan(val).len() > 2);
+
+            if is_extra {
+                bug_candidates.push(CellCandidate::from_cell(cell, val));
+            }
+        }
+    }
+
+    if bug_candidates.is_empty() {
+        return None;
+    }
+
+    // check that removing the extra candidates really leaves the bug state
+    let removed: HashSet<&CellCandidate> = bug_candidates.iter().collect();
+
+    for cell in cells.iter() {
+        let remaining = cell
+            .get_candidates()
+            .iter()
+            .filter(|&val| !removed.contains(&CellCandidate::from_cell(cell, val)))
+            .count();
+
+        if remaining != 2 {
+            return None;
+        }
+    }
+
+    for unit_type in [Row, Col, MiniGrid].iter() {
+        for k in 0..9 {
+            let unit = grid.get_unit(unit_type, k);
+
+            for val in 1..10 {
+                let count = unit
+                    .scan(val)
+                    .iter()
+                    .filter(|cell| !removed.contains(&CellCandidate::from_cell(cell, val)))
+                    .count();
+
+                if count != 0 && count != 2 {
+                    return None;
+                }
+            }
+        }
+    }
+
+    bug_candidates.sort();
+
+    Some(bug_candidates)
+}
+
+// whether target has to be false if bug is true
+fn removes(target: &CellCandidate, bug: &CellCandidate) -> bool {
+    if target.same_cell(bug) {
+        target.get_val() != bug.get_val()
+    } else {
+        target.can_see(bug, true)
+    }
+}
+
+fn make_highlights(
+    bug_candidates: &[CellCandidate],
+    to_eliminate: &[CellCandidate],
+) -> Vec<Highlight> {
+    let mut highlights = vec![];
+
+    for cell_candidate in bug_candidates.iter() {
+        highlights.push(Highlight::new_candidate_hl(
+            cell_candidate,
+            HighlightColor::NoteFg,
+            HighlightColor::NoteBg,
+        ));
+    }
+
+    for cell_candidate in to_eliminate.iter() {
+        highlights.push(Highlight::new_candidate_hl(
+            cell_candidate,
+            HighlightColor::ElimFg,
+            HighlightColor::ElimBg,
+        ));
+    }
+
+    highlights
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bug_plus_1() {
+        let bd =
+            "814956237379412685625000941140000793950000468780049512298060374567834129431297856";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let expected = vec![CellCandidate::from(4, 3, 3)];
+
+        let bug = find_bug(&grid).unwrap();
+        let to_place = bug.get_to_place().clone();
+        let to_eliminate = bug.get_to_eliminate().clone();
+
+        assert_eq!("BUG+1", bug.get_name());
+        assert_eq!(expected, to_place);
+        assert_eq!(Vec::<CellCandidate>::new(), to_eliminate);
+    }
+
+    #[test]
+    fn test_bug_plus_n() {
+        let bd =
+            "540002096291608340670004210086203450005046020402587960127435689869721534354869172";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let expected = vec![CellCandidate::from(4, 8, 3), CellCandidate::from(4, 8, 8)];
+
+        let bug = find_bug(&grid).unwrap();
+        let to_place = bug.get_to_place().clone();
+        let mut to_eliminate = bug.get_to_eliminate().clone();
+
+        to_eliminate.sort();
+
+        assert_eq!("BUG+2", bug.get_name());
+        assert_eq!(Vec::<CellCandidate>::new(), to_place);
+        assert_eq!(expected, to_eliminate);
+    }
+}
@@ -0,0 +1,196 @@
+use super::{
+    aic::{build_aics, make_highlights, AICResult, AICType},
+    link::{make_link_map, LinkType},
+    StrategyResult,
+};
+use crate::grid::Grid;
+
+use LinkType::{StrongGroupedInUnit, StrongInCell, StrongInUnit, WeakGroupedInUnit, WeakInUnit};
+
+const MAX_LENGTH: usize = 12;
+
+// An AIC on a single digit, so every link is within a unit. Grouped nodes are only tried once
+// there are no chains without them.
+pub fn find_x_chain(grid: &Grid) -> Option<StrategyResult> {
+    find_single_digit_chain(grid, false).or_else(|| find_single_digit_chain(grid, true))
+}
+
+fn find_single_digit_chain(grid: &Grid, grouped: bool) -> Option<StrategyResult> {
+    let mut strong_link_types = vec![StrongInUnit];
+    let mut weak_link_types = vec![StrongInUnit, WeakInUnit];
+
+    if grouped {
+        strong_link_types.push(StrongGroupedInUnit);
+        weak_link_types.extend([StrongGroupedInUnit, WeakGroupedInUnit]);
+    }
+
+    let strong_link_map = make_link_map(grid, &strong_link_types);
+    let weak_link_map = make_link_map(grid, &weak_link_types);
+
+    let aic_result =
+        find_shortest(|max_length| build_aics(&strong_link_map, &weak_link_map, max_length))?;
+
+    let name = match aic_result.get_aic_type() {
+        AICType::Continuous => "X-Cycle",
+        AICType::Discontinuous(_) => "X-Chain",
+    };
+
+    let name = if aic_result.is_grouped() {
+        format!("Grouped {}", name)
+    } else {
+        name.to_string()
+    };
+
+    Some(make_result(&name, &aic_result))
+}
+
+// An AIC made of bivalue cells, with the strong links within the cells and the weak links
+// between cells with the same digit
+pub fn find_xy_chain(grid: &Grid) -> Option<StrategyResult> {
+    let mut strong_link_map = make_link_map(grid, &[StrongInCell]);
+    let weak_link_map = make_link_map(grid, &[StrongInUnit, WeakInUnit]);
+
+    // a candidate with no weak links can't continue or end a chain
+    strong_link_map.retain(|node, _| weak_link_map.contains_key(node));
+
+    for links in strong_link_map.values_mut() {
+        links.retain(|node| weak_link_map.contains_key(node));
+    }
+
+    let aic_result =
+        find_shortest(|max_length| build_aics(&strong_link_map, &weak_link_map, max_length))?;
+
+    let name = match aic_result.get_aic_type() {
+        AICType::Continuous => "XY-Loop",
+        AICType::Discontinuous(_) => "XY-Chain",
+    };
+
+    Some(make_result(name, &aic_result))
+}
+
+// raises the maximum length a step at a time so that shorter chains are found first
+fn find_shortest<F>(build: F) -> Option<AICResult>
+where
+    F: Fn(usize) -> Option<AICResult>,
+{
+    (4..=MAX_LENGTH).step_by(2).find_map(build)
+}
+
+fn make_result(name: &str, aic_result: &AICResult) -> StrategyResult {
+    let length = aic_result.get_aic().len();
+
+    let highlights = make_highlights(aic_result);
+
+    StrategyResult::from_chain(
+        &format!("{} (length {})", name, length),
+        vec![],
+        aic_result.get_to_eliminate().clone(),
+        highlights,
+        length,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::CellCandidate;
+
+    #[test]
+    fn test_find_x_chain() {
+        let bd =
+            "004300000070019020000870005030000209052040830701000060200058000060730080000002700";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let expected = vec![CellCandidate::from(8, 0, 9)];
+
+        let x_chain = find_x_chain(&grid).unwrap();
+        let to_place = x_chain.get_to_place().clone();
+        let mut to_eliminate = x_chain.get_to_eliminate().clone();
+
+        to_eliminate.sort();
+
+        assert_eq!("X-Chain (length 4)", x_chain.get_name());
+        assert_eq!(Vec::<CellCandidate>::new(), to_place);
+        assert_eq!(expected, to_eliminate);
+    }
+
+    #[test]
+    fn test_find_x_cycle() {
+        let bd =
+            "008000060502810400090000071000102030030080010020905000680000050003069108010000600";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let expected = vec![
+            CellCandidate::from(7, 3, 2),
+            CellCandidate::from(8, 3, 2),
+            CellCandidate::from(8, 4, 2),
+            CellCandidate::from(8, 8, 2),
+        ];
+
+        let x_cycle = find_x_chain(&grid).unwrap();
+        let mut to_eliminate = x_cycle.get_to_eliminate().clone();
+
+        to_eliminate.sort();
+
+        assert_eq!("X-Cycle (length 4)", x_cycle.get_name());
+        assert_eq!(expected, to_eliminate);
+    }
+
+    #[test]
+    fn test_find_grouped_x_chain() {
+        let bd =
+            "000700000529008004000090080052000030307000050900030600000000002700560000003009100";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let expected = vec![CellCandidate::from(2, 6, 7), CellCandidate::from(2, 8, 7)];
+
+        let x_chain = find_x_chain(&grid).unwrap();
+        let mut to_eliminate = x_chain.get_to_eliminate().clone();
+
+        to_eliminate.sort();
+
+        assert_eq!("Grouped X-Chain (length 4)", x_chain.get_name());
+        assert_eq!(expected, to_eliminate);
+    }
+
+    #[test]
+    fn test_find_grouped_x_cycle() {
+        let bd =
+            "000000012000000003002300400001800005060070800000009000008500000900040500470006000";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let expected = vec![
+            CellCandidate::from(0, 2, 5),
+            CellCandidate::from(1, 2, 5),
+            CellCandidate::from(4, 2, 5),
+            CellCandidate::from(5, 2, 5),
+        ];
+
+        let x_cycle = find_x_chain(&grid).unwrap();
+        let mut to_eliminate = x_cycle.get_to_eliminate().clone();
+
+        to_eliminate.sort();
+
+        assert_eq!("Grouped X-Cycle (length 4)", x_cycle.get_name());
+        assert_eq!(expected, to_eliminate);
+    }
+
+    #[test]
+    fn test_find_xy_chain() {
+        let bd =
+            "004005000010900340080002009705080020000203000090050801300500090076009010000300700";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let expected = vec![CellCandidate::from(6, 2, 2), CellCandidate::from(8, 2, 2)];
+
+        let xy_chain = find_xy_chain(&grid).unwrap();
+        let to_place = xy_chain.get_to_place().clone();
+        let mut to_eliminate = xy_chain.get_to_eliminate().clone();
+
+        to_eliminate.sort();
+
+        assert_eq!("XY-Loop (length 6)", xy_chain.get_name());
+        assert_eq!(Vec::<CellCandidate>::new(), to_place);
+        assert_eq!(expected, to_eliminate);
+    }
+}
@@ -0,0 +1,333 @@
+use itertools::Itertools;
+
+use super::{
+    highlight::{Highlight, HighlightColor},
+    link::{make_link_map, LinkType},
+    medusa::{
+        color_component, get_component_starts, get_inverse_color_map, make_highlights, Color,
+        ColorMap, InverseColorMap,
+    },
+    StrategyResult,
+};
+use crate::grid::{CellCandidate, Grid};
+
+use Color::{ColorA, ColorB};
+use LinkType::StrongInUnit;
+
+// A cluster of candidates of one digit, connected by strong links and colored so that one of the
+// two colors is true
+struct Cluster {
+    val: u32,
+    color_map: ColorMap,
+    inverse_color_map: InverseColorMap,
+}
+
+impl Cluster {
+    fn get(&self, color: &Color) -> &Vec<CellCandidate> {
+        self.inverse_color_map.get(color).unwrap()
+    }
+
+    fn contains(&self, cell_candidate: &CellCandidate) -> bool {
+        self.color_map.contains_key(cell_candidate)
+    }
+}
+
+pub fn find_simple_coloring(grid: &Grid) -> Option<StrategyResult> {
+    for cluster in get_clusters(grid).iter() {
+        if let Some(res) = color_wrap(cluster) {
+            return Some(res);
+        }
+
+        if let Some(res) = color_trap(grid, cluster) {
+            return Some(res);
+        }
+    }
+
+    None
+}
+
+pub fn find_multi_coloring(grid: &Grid) -> Option<StrategyResult> {
+    let clusters = get_clusters(grid);
+
+    for (cluster, other) in clusters.iter().tuple_combinations() {
+        if cluster.val != other.val {
+            continue;
+        }
+
+        if let Some(res) = multi_color_wing(grid, cluster, other) {
+            return Some(res);
+        }
+
+        if let Some(res) = multi_color_wrap(cluster, other) {
+            return Some(res);
+        }
+
+        if let Some(res) = multi_color_wrap(other, cluster) {
+            return Some(res);
+        }
+    }
+
+    None
+}
+
+fn get_clusters(grid: &Grid) -> Vec<Cluster> {
+    let strong_link_map = make_link_map(grid, &[StrongInUnit]);
+
+    get_component_starts(&strong_link_map)
+        .iter()
+        .map(|start| {
+            let (color_map, _) = color_component(start, &strong_link_map);
+            let inverse_color_map = get_inverse_color_map(&color_map);
+
+            Cluster {
+                val: start.get_singleton().get_val(),
+                color_map,
+                inverse_color_map,
+            }
+        })
+        .collect()
+}
+
+fn sees_any(cell_candidate: &CellCandidate, others: &[CellCandidate]) -> bool {
+    others
+        .iter()
+        .any(|other| !cell_candidate.same_cell(other) && cell_candidate.can_see(other, true))
+}
+
+// Two candidates of the same color see each other, so that color is false
+fn color_wrap(cluster: &Cluster) -> Option<StrategyResult> {
+    for color in [ColorA, ColorB].iter() {
+        let wrapped = cluster
+            .get(color)
+            .iter()
+            .tuple_combinations()
+            .any(|(a, b)| a.can_see(b, true));
+
+        if !wrapped {
+            continue;
+        }
+
+        let to_place = cluster.get(&color.opposite()).clone();
+
+        let highlights = make_highlights(&cluster.color_map, Some(color), None);
+
+        return Some(StrategyResult::from(
+            "Simple Coloring - Color Wrap",
+            to_place,
+            vec![],
+            highlights,
+        ));
+    }
+
+    None
+}
+
+// A candidate outside the cluster sees both colors, so it's false whichever color is true
+fn color_trap(grid: &Grid, cluster: &Cluster) -> Option<StrategyResult> {
+    let to_eliminate: Vec<CellCandidate> = grid
+        .as_region()
+        .scan(cluster.val)
+        .iter()
+        .map(|cell| CellCandidate::from_cell(cell, cluster.val))
+        .filter(|cell_candidate| !cluster.contains(cell_candidate))
+        .filter(|cell_candidate| {
+            sees_any(cell_candidate, cluster.get(&ColorA))
+                && sees_any(cell_candidate, cluster.get(&ColorB))
+        })
+        .collect();
+
+    if to_eliminate.is_empty() {
+        return None;
+    }
+
+    let highlights = make_highlights(&cluster.color_map, None, Some(&to_eliminate));
+
+    Some(StrategyResult::from(
+        "Simple Coloring - Color Trap",
+        vec![],
+        to_eliminate,
+        highlights,
+    ))
+}
+
+// If color x of one cluster sees color y of the other, they can't both be true, so one of the
+// opposite colors is. Candidates seeing both opposite colors are false.
+fn multi_color_wing(grid: &Grid, cluster: &Cluster, other: &Cluster) -> Option<StrategyResult> {
+    for (x, y) in [ColorA, ColorB]
+        .iter()
+        .cartesian_product([ColorA, ColorB].iter())
+    {
+        let linked = cluster
+            .get(x)
+            .iter()
+            .any(|cell_candidate| sees_any(cell_candidate, other.get(y)));
+
+        if !linked {
+            continue;
+        }
+
+        let to_eliminate: Vec<CellCandidate> = grid
+            .as_region()
+            .scan(cluster.val)
+            .iter()
+            .map(|cell| CellCandidate::from_cell(cell, cluster.val))
+            .filter(|cell_candidate| !cluster.contains(cell_candidate))
+            .filter(|cell_candidate| !other.contains(cell_candidate))
+            .filter(|cell_candidate| {
+                sees_any(cell_candidate, cluster.get(&x.opposite()))
+                    && sees_any(cell_candidate, other.get(&y.opposite()))
+            })
+            .collect();
+
+        if to_eliminate.is_empty() {
+            continue;
+        }
+
+        let highlights = make_multi_highlights(cluster, other, &to_eliminate);
+
+        return Some(StrategyResult::from(
+            "Multi-Coloring - Color Wing",
+            vec![],
+            to_eliminate,
+            highlights,
+        ));
+    }
+
+    None
+}
+
+// If color x of one cluster sees both colors of the other, making it true would leave the other
+// cluster with no true color, so x is false
+fn multi_color_wrap(cluster: &Cluster, other: &Cluster) -> Option<StrategyResult> {
+    for x in [ColorA, ColorB].iter() {
+        let wrapped = [ColorA, ColorB].iter().all(|y| {
+            cluster
+                .get(x)
+                .iter()
+                .any(|cell_candidate| sees_any(cell_candidate, other.get(y)))
+        });
+
+        if !wrapped {
+            continue;
+        }
+
+        let to_eliminate = cluster.get(x).clone();
+
+        let highlights = make_multi_highlights(cluster, other, &to_eliminate);
+
+        return Some(StrategyResult::from(
+            "Multi-Coloring - Color Wrap",
+            vec![],
+            to_eliminate,
+            highlights,
+        ));
+    }
+
+    None
+}
+
+fn make_multi_highlights(
+    cluster: &Cluster,
+    other: &Cluster,
+    to_eliminate: &[CellCandidate],
+) -> Vec<Highlight> {
+    let mut highlights = make_highlights(&cluster.color_map, None, None);
+
+    for (cell_candidate, color) in other.color_map.iter() {
+        let bg = match color {
+            ColorA => HighlightColor::Orange,
+            ColorB => HighlightColor::Cyan,
+        };
+
+        highlights.push(Highlight::new_candidate_hl(
+            cell_candidate,
+            HighlightColor::Black,
+            bg,
+        ));
+    }
+
+    for cell_candidate in to_eliminate.iter() {
+        highlights.push(Highlight::new_candidate_hl(
+            cell_candidate,
+            HighlightColor::ElimFg,
+            HighlightColor::ElimBg,
+        ));
+    }
+
+    highlights
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_wrap() {
+        let bd =
+            "300400906001063800460020050040000003023090480600000090030070068007380100806002009";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let expected = vec![CellCandidate::from(4, 3, 6)];
+
+        let simple_coloring = find_simple_coloring(&grid).unwrap();
+        let mut to_place = simple_coloring.get_to_place().clone();
+        let to_eliminate = simple_coloring.get_to_eliminate().clone();
+
+        to_place.sort();
+
+        assert_eq!("Simple Coloring - Color Wrap", simple_coloring.get_name());
+        assert_eq!(expected, to_place);
+        assert_eq!(Vec::<CellCandidate>::new(), to_eliminate);
+    }
+
+    #[test]
+    fn test_color_trap() {
+        let bd =
+            "000700000529008004000090080052000030307000050900030600000000002700560000003009100";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let expected = vec![CellCandidate::from(2, 6, 7), CellCandidate::from(2, 8, 7)];
+
+        let simple_coloring = find_simple_coloring(&grid).unwrap();
+        let mut to_eliminate = simple_coloring.get_to_eliminate().clone();
+
+        to_eliminate.sort();
+
+        assert_eq!("Simple Coloring - Color Trap", simple_coloring.get_name());
+        assert_eq!(expected, to_eliminate);
+    }
+
+    #[test]
+    fn test_multi_color_wing() {
+        let bd =
+            "000239000500080007001070400070000050010000040002908700200000008060000070005746200";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let expected = vec![CellCandidate::from(3, 2, 4), CellCandidate::from(7, 0, 4)];
+
+        let multi_coloring = find_multi_coloring(&grid).unwrap();
+        let mut to_eliminate = multi_coloring.get_to_eliminate().clone();
+
+        to_eliminate.sort();
+
+        assert_eq!("Multi-Coloring - Color Wing", multi_coloring.get_name());
+        assert_eq!(expected, to_eliminate);
+    }
+
+    #[test]
+    fn test_multi_color_wrap() {
+        let bd =
+            "000020000970060205032080940020090000315472698090030012000207000083000720247308060";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let expected = vec![CellCandidate::from(3, 0, 7)];
+
+        let multi_coloring = find_multi_coloring(&grid).unwrap();
+        let mut to_eliminate = multi_coloring.get_to_eliminate().clone();
+
+        to_eliminate.sort();
+
+        assert_eq!("Multi-Coloring - Color Wrap", multi_coloring.get_name());
+        assert_eq!(expected, to_eliminate);
+    }
+}
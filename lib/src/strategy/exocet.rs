@@ -0,0 +1,265 @@
+use itertools::Itertools;
+
+use super::{
+    highlight::{Highlight, HighlightColor},
+    StrategyResult,
+};
+use crate::grid::{CellCandidate, Grid};
+use crate::util::BitSet;
+
+// Junior Exocet, described for a horizontal band (the vertical case is the same with rows and
+// columns swapped). Two base cells in one box and row hold 3 or 4 base digits between them, and
+// so two different base digits. Two target cells sit in the other two boxes of the band, outside
+// the base row. The cross lines are the columns of the targets and the column of the base box
+// without base cells.
+//
+// If every base digit is confined to at most two rows in the cross lines outside the band, then
+// a base digit a placed in a base cell has to appear inside the band in at least one cross line.
+// It can't be in the base box or the base row, so it must be in one of the target columns, and if
+// the other band cells of those columns (the companions) can't hold base digits it must be in a
+// target. Both targets are then the two digits of the base cells.
+struct Exocet {
+    transposed: bool,
+    base: [(u32, u32); 2],
+    targets: [(u32, u32); 2],
+    companions: [(u32, u32); 2],
+    base_digits: BitSet,
+}
+
+impl Exocet {
+    fn coords(&self, (row, col): (u32, u32)) -> (u32, u32) {
+        if self.transposed {
+            (col, row)
+        } else {
+            (row, col)
+        }
+    }
+}
+
+pub fn find_junior_exocet(grid: &Grid) -> Option<StrategyResult> {
+    for exocet in get_exocets(grid).iter() {
+        let to_eliminate = get_eliminations(grid, exocet);
+
+        if to_eliminate.is_empty() {
+            continue;
+        }
+
+        let highlights = make_highlights(grid, exocet, &to_eliminate);
+
+        return Some(StrategyResult::from(
+            "Junior Exocet",
+            vec![],
+            to_eliminate,
+            highlights,
+        ));
+    }
+
+    None
+}
+
+fn get_exocets(grid: &Grid) -> Vec<Exocet> {
+    let mut exocets = vec![];
+
+    for transposed in [false, true] {
+        // the placed value and candidates of a cell, with rows and columns swapped if transposed
+        let get = |row: u32, col: u32| {
+            let (row, col) = if transposed { (col, row) } else { (row, col) };
+            (grid.get_placed(row, col), grid.get_candidates(row, col))
+        };
+
+        for band in 0..3 {
+            let band_rows: Vec<u32> = (band * 3..band * 3 + 3).collect();
+
+            for (base_box, base_row) in (0..3).cartesian_product(band_rows.iter()) {
+                let box_cols: Vec<u32> = (base_box * 3..base_box * 3 + 3).collect();
+
+                for (c1, c2) in box_cols.iter().tuple_combinations() {
+                    let base = [(*base_row, *c1), (*base_row, *c2)];
+
+                    if base.iter().any(|&(row, col)| get(row, col).0 != 0) {
+                        continue;
+                    }
+
+                    let base_digits = get(base[0].0, base[0].1)
+                        .1
+                        .union(get(base[1].0, base[1].1).1);
+
+                    if !(3..=4).contains(&base_digits.len()) {
+                        continue;
+                    }
+
+                    let escape_col = *box_cols.iter().find(|&c| c != c1 && c != c2).unwrap();
+
+                    let other_rows: Vec<u32> = band_rows
+                        .iter()
+                        .filter(|&row| row != base_row)
+                        .cloned()
+                        .collect();
+
+                    let other_boxes: Vec<u32> = (0..3).filter(|&b| b != base_box).collect();
+
+                    let target_options: Vec<Vec<(u32, u32)>> = other_boxes
+                        .iter()
+                        .map(|b| {
+                            other_rows
+                                .iter()
+                                .cartesian_product(b * 3..b * 3 + 3)
+                                .map(|(row, col)| (*row, col))
+                                .filter(|&(row, col)| {
+                                    let (placed, candidates) = get(row, col);
+                                    placed == 0 && !candidates.intersection(&base_digits).is_empty()
+                                })
+                                .collect()
+                        })
+                        .collect();
+
+                    for (t1, t2) in target_options[0]
+                        .iter()
+                        .cartesian_product(target_options[1].iter())
+                    {
+                        let companions = [t1, t2].map(|&(row, col)| {
+                            let other_row = *other_rows.iter().find(|&&r| r != row).unwrap();
+                            (other_row, col)
+                        });
+
+                        let companions_clear = companions.iter().all(|&(row, col)| {
+                            let (placed, candidates) = get(row, col);
+                            !base_digits.contains(placed)
+                                && candidates.intersection(&base_digits).is_empty()
+                        });
+
+                        if !companions_clear {
+                            continue;
+                        }
+
+                        let cross_cols = [escape_col, t1.1, t2.1];
+
+                        let covered = base_digits.iter().all(|val| {
+                            let cover_rows = (0..9)
+                                .filter(|row| !band_rows.contains(row))
+                                .filter(|&row| {
+                                    cross_cols.iter().any(|&col| {
+                                        let (placed, candidates) = get(row, col);
+                                        placed == val || candidates.contains(val)
+                                    })
+                                })
+                                .count();
+
+                            cover_rows <= 2
+                        });
+
+                        if !covered {
+                            continue;
+                        }
+
+                        exocets.push(Exocet {
+                            transposed,
+                            base,
+                            targets: [*t1, *t2],
+                            companions,
+                            base_digits: base_digits.clone(),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    exocets
+}
+
+// The targets can only hold base digits, and a base digit missing from both targets can't be in
+// the base cells
+fn get_eliminations(grid: &Grid, exocet: &Exocet) -> Vec<CellCandidate> {
+    let mut to_eliminate = vec![];
+
+    let mut target_digits = BitSet::new();
+
+    for &target in exocet.targets.iter() {
+        let (row, col) = exocet.coords(target);
+        let candidates = grid.get_candidates(row, col);
+
+        target_digits.extend(candidates);
+
+        for val in candidates.difference(&exocet.base_digits).iter() {
+            to_eliminate.push(CellCandidate::from(row, col, val));
+        }
+    }
+
+    for &base in exocet.base.iter() {
+        let (row, col) = exocet.coords(base);
+
+        for val in grid
+            .get_candidates(row, col)
+            .difference(&target_digits)
+            .iter()
+        {
+            to_eliminate.push(CellCandidate::from(row, col, val));
+        }
+    }
+
+    to_eliminate
+}
+
+fn make_highlights(grid: &Grid, exocet: &Exocet, to_eliminate: &[CellCandidate]) -> Vec<Highlight> {
+    let mut highlights = vec![];
+
+    for (cells, color) in [
+        (&exocet.base, HighlightColor::Yellow),
+        (&exocet.targets, HighlightColor::Orange),
+        (&exocet.companions, HighlightColor::Cyan),
+    ] {
+        for &cell in cells.iter() {
+            let (row, col) = exocet.coords(cell);
+
+            highlights.push(Highlight::new_cell_hl(row, col, color));
+        }
+    }
+
+    for &cell in exocet.base.iter().chain(exocet.targets.iter()) {
+        let (row, col) = exocet.coords(cell);
+
+        for val in grid
+            .get_candidates(row, col)
+            .intersection(&exocet.base_digits)
+            .iter()
+        {
+            highlights.push(Highlight::new_candidate_hl(
+                &CellCandidate::from(row, col, val),
+                HighlightColor::NoteFg,
+                HighlightColor::NoteBg,
+            ));
+        }
+    }
+
+    for cell_candidate in to_eliminate.iter() {
+        highlights.push(Highlight::new_candidate_hl(
+            cell_candidate,
+            HighlightColor::ElimFg,
+            HighlightColor::ElimBg,
+        ));
+    }
+
+    highlights
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_junior_exocet() {
+        let bd =
+            "000000039000001005003050800008090006070002000100400000009080050020000600400700000";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let expected = vec![CellCandidate::from(6, 8, 3)];
+
+        let junior_exocet = find_junior_exocet(&grid).unwrap();
+        let to_place = junior_exocet.get_to_place().clone();
+        let to_eliminate = junior_exocet.get_to_eliminate().clone();
+
+        assert_eq!(Vec::<CellCandidate>::new(), to_place);
+        assert_eq!(expected, to_eliminate);
+    }
+}
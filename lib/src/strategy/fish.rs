@@ -0,0 +1,697 @@
+use itertools::Itertools;
+
+use super::{
+    highlight::{Highlight, HighlightColor},
+    StrategyResult,
+};
+use crate::grid::{CellCandidate, Grid};
+
+// rows and columns only, with minigrids among them, or any mix of the three
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum FishKind {
+    Basic,
+    Franken,
+    Mutant,
+}
+
+struct Fish {
+    val: u32,
+    size: usize,
+    kind: FishKind,
+    base: Vec<usize>,
+    cover: Vec<usize>,
+    base_mask: u128,
+    fins: u128,
+    to_eliminate: u128,
+    is_sashimi: bool,
+}
+
+pub fn find_xwing(grid: &Grid) -> Option<StrategyResult> {
+    find_fish(grid, 2, FishKind::Basic, false)
+}
+
+pub fn find_swordfish(grid: &Grid) -> Option<StrategyResult> {
+    find_fish(grid, 3, FishKind::Basic, false)
+}
+
+pub fn find_jellyfish(grid: &Grid) -> Option<StrategyResult> {
+    find_fish(grid, 4, FishKind::Basic, false)
+}
+
+// Finned and sashimi fish of every size, smallest first
+pub fn find_finned_fish(grid: &Grid) -> Option<StrategyResult> {
+    (2..5).find_map(|size| find_fish(grid, size, FishKind::Basic, true))
+}
+
+// Fish with minigrids among rows in the base and columns in the cover, or the other way around
+pub fn find_franken_fish(grid: &Grid) -> Option<StrategyResult> {
+    (2..5).find_map(|size| {
+        find_fish(grid, size, FishKind::Franken, false)
+            .or_else(|| find_fish(grid, size, FishKind::Franken, true))
+    })
+}
+
+// Fish with any mix of rows, columns and minigrids in the base and the cover
+pub fn find_mutant_fish(grid: &Grid) -> Option<StrategyResult> {
+    (2..5).find_map(|size| {
+        find_fish(grid, size, FishKind::Mutant, false)
+            .or_else(|| find_fish(grid, size, FishKind::Mutant, true))
+    })
+}
+
+// A fish of the given size is made of size base units, which share no candidates for val, and
+// size cover units holding all of the base candidates. Each base unit has val in one of the cover
+// units, which then can't have val anywhere else. Base candidates outside of the cover units are
+// fins: either one of them is true or the fish holds, so the eliminations are limited to the cells
+// seeing every fin. When finned is set only fish with fins are returned, otherwise only fish
+// without them.
+fn find_fish(grid: &Grid, size: usize, kind: FishKind, finned: bool) -> Option<StrategyResult> {
+    let peer_masks = get_peer_masks();
+
+    for val in 1..10 {
+        let unit_masks = get_unit_masks(grid, val);
+
+        for (base_units, cover_units) in get_unit_sets(kind) {
+            let search = FishSearch {
+                val,
+                unit_masks: &unit_masks,
+                peer_masks: &peer_masks,
+                size,
+                kind,
+                finned,
+                cover_units: &cover_units,
+            };
+
+            if let Some(fish) = search.find(&base_units) {
+                return Some(make_result(fish));
+            }
+        }
+    }
+
+    None
+}
+
+struct FishSearch<'a> {
+    val: u32,
+    unit_masks: &'a [u128; 27],
+    peer_masks: &'a [u128; 81],
+    size: usize,
+    kind: FishKind,
+    finned: bool,
+    cover_units: &'a [usize],
+}
+
+impl FishSearch<'_> {
+    fn find(&self, base_units: &[usize]) -> Option<Fish> {
+        let candidate_bases: Vec<usize> = base_units
+            .iter()
+            .cloned()
+            .filter(|&unit| {
+                let count = self.unit_masks[unit].count_ones() as usize;
+
+                count >= 2 && (self.finned || self.kind != FishKind::Basic || count <= self.size)
+            })
+            .collect();
+
+        self.find_base(&candidate_bases, &mut vec![], 0)
+    }
+
+    // base units are added in order, skipping any that share candidates with the ones before
+    fn find_base(
+        &self,
+        candidate_bases: &[usize],
+        base: &mut Vec<usize>,
+        base_mask: u128,
+    ) -> Option<Fish> {
+        if base.len() == self.size {
+            return self.find_cover(base, base_mask, &mut vec![], 0, 0);
+        }
+
+        for (i, &unit) in candidate_bases.iter().enumerate() {
+            if base_mask & self.unit_masks[unit] != 0 {
+                continue;
+            }
+
+            base.push(unit);
+            let fish = self.find_base(
+                &candidate_bases[i + 1..],
+                base,
+                base_mask | self.unit_masks[unit],
+            );
+            base.pop();
+
+            if fish.is_some() {
+                return fish;
+            }
+        }
+
+        None
+    }
+
+    // The first uncovered base candidate has to be covered by one of its units, unless it's a fin,
+    // so the cover is built up one of those units at a time.
+    fn find_cover(
+        &self,
+        base: &[usize],
+        base_mask: u128,
+        cover: &mut Vec<usize>,
+        cover_mask: u128,
+        fins: u128,
+    ) -> Option<Fish> {
+        let uncovered = base_mask & !cover_mask & !fins;
+
+        if uncovered == 0 || cover.len() == self.size {
+            if cover.len() != self.size {
+                return None;
+            }
+
+            return self.check(base, base_mask, cover, cover_mask);
+        }
+
+        if !self.can_cover(base, cover, uncovered, fins) {
+            return None;
+        }
+
+        let i = uncovered.trailing_zeros() as usize;
+
+        for unit in [i / 9, 9 + i % 9, get_minigrid_unit(i)] {
+            if !self.cover_units.contains(&unit) || base.contains(&unit) || cover.contains(&unit) {
+                continue;
+            }
+
+            cover.push(unit);
+            let fish = self.find_cover(
+                base,
+                base_mask,
+                cover,
+                cover_mask | self.unit_masks[unit],
+                fins,
+            );
+            cover.pop();
+
+            if fish.is_some() {
+                return fish;
+            }
+        }
+
+        if self.finned {
+            let fins = fins | (1 << i);
+
+            if self.is_fin_group(fins) {
+                return self.find_cover(base, base_mask, cover, cover_mask, fins);
+            }
+        }
+
+        None
+    }
+
+    // Whether the units left to add to the cover could hold the uncovered candidates, with the
+    // help of fins in a single minigrid. Most bases are ruled out by this long before the cover is
+    // complete.
+    fn can_cover(&self, base: &[usize], cover: &[usize], uncovered: u128, fins: u128) -> bool {
+        let max_per_unit = self
+            .cover_units
+            .iter()
+            .filter(|unit| !base.contains(unit) && !cover.contains(unit))
+            .map(|&unit| (self.unit_masks[unit] & uncovered).count_ones())
+            .max()
+            .unwrap_or(0);
+
+        let max_fins = if !self.finned {
+            0
+        } else if fins != 0 {
+            let i = fins.trailing_zeros() as usize;
+
+            (self.unit_masks[get_minigrid_unit(i)] & uncovered).count_ones()
+        } else {
+            (18..27)
+                .map(|unit| (self.unit_masks[unit] & uncovered).count_ones())
+                .max()
+                .unwrap_or(0)
+        };
+
+        let remaining = (self.size - cover.len()) as u32;
+
+        uncovered.count_ones() <= remaining * max_per_unit + max_fins
+    }
+
+    // like the fins of a basic fish, the fins have to share a minigrid
+    fn is_fin_group(&self, fins: u128) -> bool {
+        let i = fins.trailing_zeros() as usize;
+
+        fins & !self.unit_masks[get_minigrid_unit(i)] == 0
+    }
+
+    fn check(
+        &self,
+        base: &[usize],
+        base_mask: u128,
+        cover: &[usize],
+        cover_mask: u128,
+    ) -> Option<Fish> {
+        let fins = base_mask & !cover_mask;
+
+        if (fins != 0) != self.finned || !is_kind(self.kind, base, cover) {
+            return None;
+        }
+
+        if fins != 0 && !self.is_fin_group(fins) {
+            return None;
+        }
+
+        // every base unit needs a candidate in the cover units for a fish to be there once the
+        // fins are removed
+        if base
+            .iter()
+            .any(|&unit| self.unit_masks[unit] & cover_mask == 0)
+        {
+            return None;
+        }
+
+        if self.splits(base, cover) {
+            return None;
+        }
+
+        let mut to_eliminate = cover_mask & !base_mask;
+
+        for i in get_indices(fins) {
+            to_eliminate &= self.peer_masks[i];
+        }
+
+        if to_eliminate == 0 {
+            return None;
+        }
+
+        // without its fins, a sashimi fish has a base unit with a single candidate
+        let is_sashimi = base
+            .iter()
+            .any(|&unit| (self.unit_masks[unit] & !fins).count_ones() == 1);
+
+        Some(Fish {
+            val: self.val,
+            size: self.size,
+            kind: self.kind,
+            base: base.to_vec(),
+            cover: cover.iter().cloned().sorted().collect(),
+            base_mask,
+            fins,
+            to_eliminate,
+            is_sashimi,
+        })
+    }
+
+    // Whether some of the base units have all of their candidates in as many of the cover units,
+    // making them a smaller fish of their own. Its eliminations leave the rest of the base to
+    // another small fish, so the pattern isn't worth a bigger one.
+    fn splits(&self, base: &[usize], cover: &[usize]) -> bool {
+        (1..base.len()).any(|size| {
+            base.iter().combinations(size).any(|units| {
+                let mask = units
+                    .iter()
+                    .fold(0, |mask, &&unit| mask | self.unit_masks[unit]);
+
+                let touched: Vec<usize> = cover
+                    .iter()
+                    .cloned()
+                    .filter(|&unit| self.unit_masks[unit] & mask != 0)
+                    .collect();
+
+                let cover_mask = touched
+                    .iter()
+                    .fold(0, |cover_mask, &unit| cover_mask | self.unit_masks[unit]);
+
+                touched.len() <= size && mask & !cover_mask == 0
+            })
+        })
+    }
+}
+
+// pairs of the units the base and the cover are picked from
+fn get_unit_sets(kind: FishKind) -> Vec<(Vec<usize>, Vec<usize>)> {
+    let rows: Vec<usize> = (0..9).collect();
+    let cols: Vec<usize> = (9..18).collect();
+    let minigrids: Vec<usize> = (18..27).collect();
+
+    match kind {
+        FishKind::Basic => vec![(rows.clone(), cols.clone()), (cols, rows)],
+        FishKind::Franken => {
+            let rows_minigrids = [rows, minigrids.clone()].concat();
+            let cols_minigrids = [cols, minigrids].concat();
+
+            vec![
+                (rows_minigrids.clone(), cols_minigrids.clone()),
+                (cols_minigrids, rows_minigrids),
+            ]
+        }
+        FishKind::Mutant => {
+            let all: Vec<usize> = (0..27).collect();
+
+            vec![(all.clone(), all)]
+        }
+    }
+}
+
+// whether the fish is of the kind being looked for rather than a simpler one
+fn is_kind(kind: FishKind, base: &[usize], cover: &[usize]) -> bool {
+    let is_minigrid = |unit: &usize| *unit >= 18;
+
+    let is_franken_shaped = |base_type: usize, cover_type: usize| {
+        base.iter()
+            .all(|unit| is_minigrid(unit) || unit / 9 == base_type)
+            && cover
+                .iter()
+                .all(|unit| is_minigrid(unit) || unit / 9 == cover_type)
+    };
+
+    match kind {
+        FishKind::Basic => true,
+        FishKind::Franken => base.iter().chain(cover.iter()).any(is_minigrid),
+        FishKind::Mutant => !is_franken_shaped(0, 1) && !is_franken_shaped(1, 0),
+    }
+}
+
+// Units are numbered 0-8 for the rows, 9-17 for the columns and 18-26 for the minigrids. For a
+// given value, each unit is represented by a mask of the cells (bit 9 * row + col) that can hold it.
+pub(super) fn get_unit_masks(grid: &Grid, val: u32) -> [u128; 27] {
+    let mut unit_masks = [0; 27];
+
+    for row in 0..9 {
+        for col in 0..9 {
+            if grid.get_placed(row, col) != 0 || !grid.get_candidates(row, col).contains(val) {
+                continue;
+            }
+
+            let i = (9 * row + col) as usize;
+
+            unit_masks[i / 9] |= 1 << i;
+            unit_masks[9 + i % 9] |= 1 << i;
+            unit_masks[get_minigrid_unit(i)] |= 1 << i;
+        }
+    }
+
+    unit_masks
+}
+
+// for every cell, the mask of the other cells that share a unit with it
+fn get_peer_masks() -> [u128; 81] {
+    let mut peer_masks = [0; 81];
+
+    for (i, peer_mask) in peer_masks.iter_mut().enumerate() {
+        for j in 0..81 {
+            let same_row = i / 9 == j / 9;
+            let same_col = i % 9 == j % 9;
+            let same_minigrid = (i / 27, (i % 9) / 3) == (j / 27, (j % 9) / 3);
+
+            if i != j && (same_row || same_col || same_minigrid) {
+                *peer_mask |= 1 << j;
+            }
+        }
+    }
+
+    peer_masks
+}
+
+// the unit number of the minigrid containing cell i
+fn get_minigrid_unit(i: usize) -> usize {
+    18 + 3 * (i / 27) + (i % 9) / 3
+}
+
+fn get_indices(mask: u128) -> impl Iterator<Item = usize> {
+    (0..81).filter(move |i| mask & (1 << i) != 0)
+}
+
+pub(super) fn get_cell_candidates(mask: u128, val: u32) -> Vec<CellCandidate> {
+    get_indices(mask)
+        .map(|i| CellCandidate::from(i as u32 / 9, i as u32 % 9, val))
+        .collect()
+}
+
+fn get_fish_name(size: usize) -> &'static str {
+    match size {
+        2 => "X-Wing",
+        3 => "Swordfish",
+        4 => "Jellyfish",
+        _ => unreachable!(),
+    }
+}
+
+// r1, c1 and b1 for the first row, column and minigrid
+fn get_unit_name(unit: usize) -> String {
+    let prefix = ["r", "c", "b"][unit / 9];
+
+    format!("{}{}", prefix, unit % 9 + 1)
+}
+
+fn get_name(fish: &Fish) -> String {
+    let fin_type = if fish.fins == 0 {
+        ""
+    } else if fish.is_sashimi {
+        "Sashimi "
+    } else {
+        "Finned "
+    };
+
+    let kind = match fish.kind {
+        FishKind::Basic => "",
+        FishKind::Franken => "Franken ",
+        FishKind::Mutant => "Mutant ",
+    };
+
+    let name = format!("{}{}{}", fin_type, kind, get_fish_name(fish.size));
+
+    if fish.kind == FishKind::Basic {
+        return name;
+    }
+
+    let base = fish.base.iter().map(|&unit| get_unit_name(unit)).join(" ");
+    let cover = fish.cover.iter().map(|&unit| get_unit_name(unit)).join(" ");
+
+    format!("{} ({} / {})", name, base, cover)
+}
+
+fn make_result(fish: Fish) -> StrategyResult {
+    let to_eliminate = get_cell_candidates(fish.to_eliminate, fish.val);
+
+    let highlights = make_highlights(&fish, &to_eliminate);
+
+    StrategyResult::from(&get_name(&fish), vec![], to_eliminate, highlights)
+}
+
+fn make_highlights(fish: &Fish, to_eliminate: &[CellCandidate]) -> Vec<Highlight> {
+    let mut highlights = vec![];
+
+    let body = get_cell_candidates(fish.base_mask & !fish.fins, fish.val);
+    let fins = get_cell_candidates(fish.fins, fish.val);
+
+    for (cell_candidates, cell_color, fg, bg) in [
+        (
+            body,
+            HighlightColor::Orange,
+            HighlightColor::NoteFg,
+            HighlightColor::NoteBg,
+        ),
+        (
+            fins,
+            HighlightColor::Cyan,
+            HighlightColor::NoteSecondaryFg,
+            HighlightColor::NoteSecondaryBg,
+        ),
+    ] {
+        for cell_candidate in cell_candidates.iter() {
+            let (row, col, _) = cell_candidate.as_tuple();
+
+            highlights.push(Highlight::new_cell_hl(row, col, cell_color));
+            highlights.push(Highlight::new_candidate_hl(cell_candidate, fg, bg));
+        }
+    }
+
+    for cell_candidate in to_eliminate.iter() {
+        highlights.push(Highlight::new_candidate_hl(
+            cell_candidate,
+            HighlightColor::ElimFg,
+            HighlightColor::ElimBg,
+        ));
+    }
+
+    highlights
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_xwing_rows() {
+        let bd =
+            "000910040060007508000000201090005100000030000003800070607000000584200010030086000";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let expected = vec![
+            CellCandidate::from(3, 8, 6),
+            CellCandidate::from(4, 6, 6),
+            CellCandidate::from(4, 8, 6),
+            CellCandidate::from(5, 6, 6),
+            CellCandidate::from(5, 8, 6),
+        ];
+
+        let xwing = find_xwing(&grid).unwrap();
+        let to_place = xwing.get_to_place().clone();
+        let mut to_eliminate = xwing.get_to_eliminate().clone();
+
+        to_eliminate.sort();
+
+        assert_eq!(Vec::<CellCandidate>::new(), to_place);
+        assert_eq!(expected, to_eliminate);
+    }
+
+    #[test]
+    fn test_find_xwing_cols() {
+        let bd =
+            "300910040060307598000650231090005103000030803003860070607000380584203010030086000";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let expected = vec![CellCandidate::from(8, 6, 9), CellCandidate::from(8, 8, 9)];
+
+        let xwing = find_xwing(&grid).unwrap();
+        let to_place = xwing.get_to_place().clone();
+        let mut to_eliminate = xwing.get_to_eliminate().clone();
+
+        to_eliminate.sort();
+
+        assert_eq!(Vec::<CellCandidate>::new(), to_place);
+        assert_eq!(expected, to_eliminate);
+    }
+
+    #[test]
+    fn test_find_swordfish() {
+        let bd =
+            "200709006190000002080002030670503040409000305350904060060300090800090053900407001";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let mut expected = vec![
+            CellCandidate::from(4, 4, 2),
+            CellCandidate::from(7, 2, 2),
+            CellCandidate::from(8, 2, 2),
+            CellCandidate::from(8, 4, 2),
+            CellCandidate::from(7, 6, 2),
+            CellCandidate::from(8, 6, 2),
+        ];
+        expected.sort();
+
+        let swordfish = find_swordfish(&grid).unwrap();
+        let to_place = swordfish.get_to_place().clone();
+        let mut to_eliminate = swordfish.get_to_eliminate().clone();
+
+        to_eliminate.sort();
+
+        assert_eq!(Vec::<CellCandidate>::new(), to_place);
+        assert_eq!(expected, to_eliminate);
+    }
+
+    #[test]
+    fn test_find_jellyfish() {
+        let bd =
+            "004005000010900340080002009705080020000203000090050801300500090076009010000300700";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let expected = vec![
+            CellCandidate::from(8, 0, 5),
+            CellCandidate::from(8, 7, 5),
+            CellCandidate::from(8, 8, 5),
+        ];
+
+        let jellyfish = find_jellyfish(&grid).unwrap();
+        let mut to_eliminate = jellyfish.get_to_eliminate().clone();
+
+        to_eliminate.sort();
+
+        assert_eq!("Jellyfish", jellyfish.get_name());
+        assert_eq!(expected, to_eliminate);
+    }
+
+    #[test]
+    fn test_find_finned_xwing() {
+        let bd =
+            "004005000010900340080002009705080020000203000090050801300500090076009010000300700";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let expected = vec![CellCandidate::from(8, 8, 5)];
+
+        let finned_fish = find_finned_fish(&grid).unwrap();
+        let to_eliminate = finned_fish.get_to_eliminate().clone();
+
+        assert_eq!("Finned X-Wing", finned_fish.get_name());
+        assert_eq!(expected, to_eliminate);
+    }
+
+    #[test]
+    fn test_find_sashimi_swordfish() {
+        let bd =
+            "008000500020805090000106000705000203060070080000903000510307028002050300030000010";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let expected = vec![CellCandidate::from(4, 5, 1)];
+
+        let finned_fish = find_finned_fish(&grid).unwrap();
+        let to_eliminate = finned_fish.get_to_eliminate().clone();
+
+        assert_eq!("Sashimi Swordfish", finned_fish.get_name());
+        assert_eq!(expected, to_eliminate);
+    }
+
+    #[test]
+    fn test_find_franken_fish() {
+        let bd =
+            "300000020064500000000901604010007300700000001005800090501603000000002810080000002";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let expected = vec![
+            CellCandidate::from(3, 4, 2),
+            CellCandidate::from(4, 4, 2),
+            CellCandidate::from(5, 4, 2),
+        ];
+
+        let franken_fish = find_franken_fish(&grid).unwrap();
+        let to_eliminate = franken_fish.get_to_eliminate().clone();
+
+        assert_eq!("Franken X-Wing (r2 r3 / c5 b1)", franken_fish.get_name());
+        assert_eq!(expected, to_eliminate);
+    }
+
+    #[test]
+    fn test_find_mutant_fish() {
+        let bd =
+            "501803002063002080827690301316000208204008706708006013035061800672580134180430005";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let expected = vec![
+            CellCandidate::from(1, 6, 9),
+            CellCandidate::from(6, 3, 9),
+            CellCandidate::from(6, 7, 9),
+        ];
+
+        let mutant_fish = find_mutant_fish(&grid).unwrap();
+        let mut to_eliminate = mutant_fish.get_to_eliminate().clone();
+
+        to_eliminate.sort();
+
+        assert_eq!(
+            "Mutant Swordfish (r1 c1 c9 / r7 b1 b3)",
+            mutant_fish.get_name()
+        );
+        assert_eq!(expected, to_eliminate);
+    }
+
+    #[test]
+    fn test_mutant_fish_skips_split_fish() {
+        // r4 and c7 hold a Mutant X-Wing over b3 and b5, but each of them is a box-line reduction
+        // on its own
+        let bd =
+            "004005000010900340080002009705080020000203000090050801300500090076009010000300700";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let mutant_fish = find_mutant_fish(&grid).unwrap();
+
+        assert_ne!("Mutant X-Wing (r4 c7 / b3 b5)", mutant_fish.get_name());
+    }
+}
@@ -0,0 +1,443 @@
+use std::collections::{HashMap, HashSet};
+
+use super::{
+    highlight::{Highlight, HighlightColor},
+    StrategyResult,
+};
+use crate::grid::{CellCandidate, Grid};
+
+// cells and units with more candidates than this aren't tried as forcing chain starts
+const MAX_BRANCHES: usize = 3;
+
+const BRANCH_COLORS: [HighlightColor; MAX_BRANCHES] = [
+    HighlightColor::Orange,
+    HighlightColor::Cyan,
+    HighlightColor::Magenta,
+];
+
+type Implication = (CellCandidate, CellCandidate);
+
+// What follows from placing a candidate, found by repeatedly placing naked and hidden singles on a
+// copy of the grid. Each placement is linked to the one before it that it sees, which is what's
+// shown as the implication tree.
+struct Branch {
+    assumption: CellCandidate,
+    grid: Grid,
+    placed: Vec<CellCandidate>,
+    implications: Vec<Implication>,
+    is_contradiction: bool,
+}
+
+impl Branch {
+    fn from(grid: &Grid, assumption: &CellCandidate) -> Branch {
+        let mut branch = Branch {
+            assumption: assumption.clone(),
+            grid: grid.clone(),
+            placed: vec![assumption.clone()],
+            implications: vec![],
+            is_contradiction: false,
+        };
+
+        branch.grid.place(assumption);
+        branch.propagate();
+
+        branch
+    }
+
+    fn propagate(&mut self) {
+        loop {
+            if has_contradiction(&self.grid) {
+                self.is_contradiction = true;
+                return;
+            }
+
+            let Some(next) = find_single(&self.grid) else {
+                return;
+            };
+
+            let cause = self
+                .placed
+                .iter()
+                .rev()
+                .find(|placed| placed.can_see(&next, false))
+                .unwrap_or(&self.assumption)
+                .clone();
+
+            self.grid.place(&next);
+            self.implications.push((cause, next.clone()));
+            self.placed.push(next);
+        }
+    }
+
+    fn is_true(&self, cell_candidate: &CellCandidate) -> bool {
+        let (r, c, val) = cell_candidate.as_tuple();
+
+        self.grid.get_placed(r, c) == val
+    }
+
+    fn is_false(&self, cell_candidate: &CellCandidate) -> bool {
+        let (r, c, val) = cell_candidate.as_tuple();
+
+        !self.is_true(cell_candidate) && !self.grid.get_candidates(r, c).contains(val)
+    }
+
+    // the implications leading from the assumption to the given placements and eliminations
+    fn get_tree(
+        &self,
+        to_place: &[CellCandidate],
+        to_eliminate: &[CellCandidate],
+    ) -> Vec<Implication> {
+        let mut needed: HashSet<CellCandidate> = to_place.iter().cloned().collect();
+
+        for cell_candidate in to_eliminate.iter() {
+            let cause = self.placed.iter().find(|placed| {
+                placed.same_cell(cell_candidate) || placed.can_see(cell_candidate, true)
+            });
+
+            if let Some(placed) = cause {
+                needed.insert(placed.clone());
+            }
+        }
+
+        let mut tree = vec![];
+
+        for (cause, effect) in self.implications.iter().rev() {
+            if needed.contains(effect) {
+                needed.insert(cause.clone());
+                tree.push((cause.clone(), effect.clone()));
+            }
+        }
+
+        tree
+    }
+}
+
+// Assumes each candidate of a cell or each position of a digit in a unit, for cells and units with
+// only a few of them. If one assumption leads to a contradiction it's false. Otherwise whatever
+// follows from all of them is true.
+pub fn find_forcing_chain(grid: &Grid) -> Option<StrategyResult> {
+    let branches = get_branches(grid);
+
+    find_contradiction(&branches)
+        .or_else(|| find_cell_forcing_chain(grid, &branches))
+        .or_else(|| find_unit_forcing_chain(grid, &branches))
+}
+
+fn get_branches(grid: &Grid) -> HashMap<CellCandidate, Branch> {
+    let mut branches = HashMap::new();
+
+    for cell_candidate in get_cell_candidates(grid).iter() {
+        branches.insert(cell_candidate.clone(), Branch::from(grid, cell_candidate));
+    }
+
+    branches
+}
+
+fn find_contradiction(branches: &HashMap<CellCandidate, Branch>) -> Option<StrategyResult> {
+    let mut assumptions: Vec<&CellCandidate> = branches.keys().collect();
+    assumptions.sort();
+
+    for assumption in assumptions {
+        let branch = branches.get(assumption).unwrap();
+
+        if !branch.is_contradiction {
+            continue;
+        }
+
+        let to_eliminate = vec![assumption.clone()];
+
+        let mut highlights = make_branch_highlights(branch, &branch.implications, 0);
+
+        highlights.push(Highlight::new_candidate_hl(
+            assumption,
+            HighlightColor::ElimFg,
+            HighlightColor::ElimBg,
+        ));
+
+        return Some(StrategyResult::from(
+            "Contradiction Forcing Chain",
+            vec![],
+            to_eliminate,
+            highlights,
+        ));
+    }
+
+    None
+}
+
+fn find_cell_forcing_chain(
+    grid: &Grid,
+    branches: &HashMap<CellCandidate, Branch>,
+) -> Option<StrategyResult> {
+    for r in 0..9 {
+        for c in 0..9 {
+            let candidates = grid.get_candidates(r, c);
+
+            // a single candidate is a naked single
+            if candidates.len() < 2 || candidates.len() as usize > MAX_BRANCHES {
+                continue;
+            }
+
+            let starts: Vec<CellCandidate> = candidates
+                .iter()
+                .map(|val| CellCandidate::from(r, c, val))
+                .collect();
+
+            if let Some(res) = check_branches(grid, branches, &starts, "Cell Forcing Chain") {
+                return Some(res);
+            }
+        }
+    }
+
+    None
+}
+
+fn find_unit_forcing_chain(
+    grid: &Grid,
+    branches: &HashMap<CellCandidate, Branch>,
+) -> Option<StrategyResult> {
+    for unit in 0..27 {
+        for val in 1..10 {
+            let starts: Vec<CellCandidate> = get_unit_coords(unit)
+                .into_iter()
+                .filter(|&(r, c)| grid.get_candidates(r, c).contains(val))
+                .map(|(r, c)| CellCandidate::from(r, c, val))
+                .collect();
+
+            // a single position is a hidden single
+            if starts.len() < 2 || starts.len() > MAX_BRANCHES {
+                continue;
+            }
+
+            if let Some(res) = check_branches(grid, branches, &starts, "Unit Forcing Chain") {
+                return Some(res);
+            }
+        }
+    }
+
+    None
+}
+
+// One of the starts has to be true, so anything that follows from all of them is true too
+fn check_branches(
+    grid: &Grid,
+    branches: &HashMap<CellCandidate, Branch>,
+    starts: &[CellCandidate],
+    name: &str,
+) -> Option<StrategyResult> {
+    let branches: Vec<&Branch> = starts
+        .iter()
+        .map(|start| branches.get(start).unwrap())
+        .collect();
+
+    if branches.iter().any(|branch| branch.is_contradiction) {
+        return None;
+    }
+
+    // the branches can agree on a large part of the grid, so only the first placement is
+    // reported to keep the step explainable
+    let to_place: Vec<CellCandidate> = branches[0]
+        .placed
+        .iter()
+        .find(|cell_candidate| branches.iter().all(|branch| branch.is_true(cell_candidate)))
+        .into_iter()
+        .cloned()
+        .collect();
+
+    let to_eliminate: Vec<CellCandidate> = if to_place.is_empty() {
+        get_cell_candidates(grid)
+            .into_iter()
+            .filter(|cell_candidate| {
+                branches
+                    .iter()
+                    .all(|branch| branch.is_false(cell_candidate))
+            })
+            .collect()
+    } else {
+        vec![]
+    };
+
+    if to_place.is_empty() && to_eliminate.is_empty() {
+        return None;
+    }
+
+    let mut highlights = vec![];
+
+    for (i, branch) in branches.iter().enumerate() {
+        let tree = branch.get_tree(&to_place, &to_eliminate);
+
+        highlights.extend(make_branch_highlights(branch, &tree, i));
+    }
+
+    for cell_candidate in to_place.iter() {
+        highlights.push(Highlight::new_candidate_hl(
+            cell_candidate,
+            HighlightColor::NoteSecondaryFg,
+            HighlightColor::NoteSecondaryBg,
+        ));
+    }
+
+    for cell_candidate in to_eliminate.iter() {
+        highlights.push(Highlight::new_candidate_hl(
+            cell_candidate,
+            HighlightColor::ElimFg,
+            HighlightColor::ElimBg,
+        ));
+    }
+
+    Some(StrategyResult::from(
+        name,
+        to_place,
+        to_eliminate,
+        highlights,
+    ))
+}
+
+fn get_cell_candidates(grid: &Grid) -> Vec<CellCandidate> {
+    let mut cell_candidates = vec![];
+
+    for r in 0..9 {
+        for c in 0..9 {
+            for val in grid.get_candidates(r, c).iter() {
+                cell_candidates.push(CellCandidate::from(r, c, val));
+            }
+        }
+    }
+
+    cell_candidates
+}
+
+// units are numbered 0-8 for rows, 9-17 for columns and 18-26 for minigrids
+fn get_unit_coords(unit: u32) -> Vec<(u32, u32)> {
+    match unit {
+        0..=8 => (0..9).map(|c| (unit, c)).collect(),
+        9..=17 => (0..9).map(|r| (r, unit - 9)).collect(),
+        _ => {
+            let (cr, cc) = (((unit - 18) / 3) * 3, ((unit - 18) % 3) * 3);
+
+            (0..9).map(|i| (cr + i / 3, cc + i % 3)).collect()
+        }
+    }
+}
+
+// an empty cell with no candidates left, or a digit with nowhere to go in a unit
+fn has_contradiction(grid: &Grid) -> bool {
+    for r in 0..9 {
+        for c in 0..9 {
+            if grid.get_placed(r, c) == 0 && grid.get_candidates(r, c).is_empty() {
+                return true;
+            }
+        }
+    }
+
+    (0..27).any(|unit| {
+        let coords = get_unit_coords(unit);
+
+        (1..10).any(|val| {
+            coords.iter().all(|&(r, c)| {
+                grid.get_placed(r, c) != val && !grid.get_candidates(r, c).contains(val)
+            })
+        })
+    })
+}
+
+fn find_single(grid: &Grid) -> Option<CellCandidate> {
+    for r in 0..9 {
+        for c in 0..9 {
+            let candidates = grid.get_candidates(r, c);
+
+            if candidates.len() == 1 {
+                return Some(CellCandidate::from(r, c, candidates.get_smallest()));
+            }
+        }
+    }
+
+    for unit in 0..27 {
+        let coords = get_unit_coords(unit);
+
+        for val in 1..10 {
+            let positions: Vec<&(u32, u32)> = coords
+                .iter()
+                .filter(|&&(r, c)| grid.get_candidates(r, c).contains(val))
+                .collect();
+
+            if let [&(r, c)] = positions[..] {
+                return Some(CellCandidate::from(r, c, val));
+            }
+        }
+    }
+
+    None
+}
+
+fn make_branch_highlights(branch: &Branch, tree: &[Implication], i: usize) -> Vec<Highlight> {
+    let mut highlights = vec![Highlight::new_candidate_hl(
+        &branch.assumption,
+        HighlightColor::NoteFg,
+        HighlightColor::NoteBg,
+    )];
+
+    for (cause, effect) in tree.iter() {
+        highlights.push(Highlight::new_line_hl(
+            cause,
+            effect,
+            BRANCH_COLORS[i],
+            false,
+        ));
+    }
+
+    highlights
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contradiction_forcing_chain() {
+        let bd =
+            "004005000010900340080002009705080020000203000090050801300500090076009010000300700";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let forcing_chain = find_forcing_chain(&grid).unwrap();
+
+        assert_eq!("Contradiction Forcing Chain", forcing_chain.get_name());
+        assert_eq!(Vec::<CellCandidate>::new(), *forcing_chain.get_to_place());
+        assert_eq!(
+            vec![CellCandidate::from(0, 0, 2)],
+            *forcing_chain.get_to_eliminate()
+        );
+    }
+
+    #[test]
+    fn test_cell_forcing_chain() {
+        let bd =
+            "300000020064500000000901604010007300700000001005800090501603000000002810080000002";
+        let grid = Grid::from_str(bd).unwrap();
+        let branches = get_branches(&grid);
+
+        let forcing_chain = find_cell_forcing_chain(&grid, &branches).unwrap();
+
+        assert_eq!("Cell Forcing Chain", forcing_chain.get_name());
+        assert_eq!(
+            vec![CellCandidate::from(1, 5, 8)],
+            *forcing_chain.get_to_place()
+        );
+    }
+
+    #[test]
+    fn test_unit_forcing_chain() {
+        let bd =
+            "000060570320007040700410000030000400106000803004000010000094001010700059098030000";
+        let grid = Grid::from_str(bd).unwrap();
+        let branches = get_branches(&grid);
+
+        let forcing_chain = find_unit_forcing_chain(&grid, &branches).unwrap();
+
+        assert_eq!("Unit Forcing Chain", forcing_chain.get_name());
+        assert_eq!(
+            vec![CellCandidate::from(0, 2, 1)],
+            *forcing_chain.get_to_place()
+        );
+    }
+}
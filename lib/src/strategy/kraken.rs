@@ -0,0 +1,242 @@
+use itertools::Itertools;
+
+use std::collections::{HashMap, VecDeque};
+
+use super::{
+    fish::{get_cell_candidates, get_unit_masks},
+    highlight::{Highlight, HighlightColor},
+    link::{make_link_map, LinkMap, LinkType},
+    StrategyResult,
+};
+use crate::grid::{CellCandidate, Grid};
+
+use LinkType::{StrongInCell, StrongInUnit, WeakInCell, WeakInUnit};
+
+// more fins make for chains too long to follow, and take a long time to search through
+const MAX_FINS: u32 = 3;
+
+// the candidates each candidate is linked to, sorted so that the chains found are always the same
+type Links = HashMap<CellCandidate, Vec<CellCandidate>>;
+
+// what a candidate being on (true) or off (false) was implied by
+type Implications = HashMap<(CellCandidate, bool), Option<(CellCandidate, bool)>>;
+
+// A finned X-Wing or Swordfish whose fins don't all see the candidates it would eliminate. Either
+// none of the fins is true, and the fish eliminates val from the rest of its cover lines, or one of
+// the fins is. If every fin leads through a chain to the same candidate being false, it's false
+// either way.
+pub fn find_kraken_fish(grid: &Grid) -> Option<StrategyResult> {
+    let strong_links = get_links(&make_link_map(grid, &[StrongInCell, StrongInUnit]));
+    let weak_links = get_links(&make_link_map(grid, &[WeakInCell, WeakInUnit]));
+
+    let mut implications_from = HashMap::new();
+
+    for size in 2..=3 {
+        for val in 1..10 {
+            let unit_masks = get_unit_masks(grid, val);
+
+            for (base_lines, cover_lines) in [(0..9, 9..18), (9..18, 0..9)] {
+                let bases = base_lines.filter(|&line| unit_masks[line].count_ones() >= 2);
+
+                for base in bases.combinations(size) {
+                    let base_mask = base.iter().fold(0, |mask, &line| mask | unit_masks[line]);
+
+                    let covers = cover_lines
+                        .clone()
+                        .filter(|&line| unit_masks[line] & base_mask != 0);
+
+                    for cover in covers.combinations(size) {
+                        let cover_mask =
+                            cover.iter().fold(0, |mask, &line| mask | unit_masks[line]);
+
+                        let fins = base_mask & !cover_mask;
+                        let targets = cover_mask & !base_mask;
+
+                        if fins == 0 || fins.count_ones() > MAX_FINS || targets == 0 {
+                            continue;
+                        }
+
+                        let fins = get_cell_candidates(fins, val);
+
+                        for fin in fins.iter() {
+                            implications_from.entry(fin.clone()).or_insert_with(|| {
+                                get_implications(fin, &strong_links, &weak_links)
+                            });
+                        }
+
+                        let to_eliminate: Vec<CellCandidate> = get_cell_candidates(targets, val)
+                            .into_iter()
+                            // targets seen by every fin are left to the finned fish
+                            .filter(|target| !fins.iter().all(|fin| fin.can_see(target, false)))
+                            .filter(|target| {
+                                fins.iter().all(|fin| {
+                                    implications_from[fin].contains_key(&(target.clone(), false))
+                                })
+                            })
+                            .collect();
+
+                        if to_eliminate.is_empty() {
+                            continue;
+                        }
+
+                        let body = get_cell_candidates(base_mask & cover_mask, val);
+
+                        let highlights =
+                            make_highlights(&body, &fins, &implications_from, &to_eliminate);
+
+                        let name = match size {
+                            2 => "Kraken X-Wing",
+                            3 => "Kraken Swordfish",
+                            _ => unreachable!(),
+                        };
+
+                        return Some(StrategyResult::from(name, vec![], to_eliminate, highlights));
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
+fn get_links(link_map: &LinkMap) -> Links {
+    link_map
+        .iter()
+        .map(|(node, linked)| {
+            let linked = linked
+                .iter()
+                .map(|other| other.get_singleton().clone())
+                .sorted()
+                .collect();
+
+            (node.get_singleton().clone(), linked)
+        })
+        .collect()
+}
+
+// Everything that follows from fin being true, found breadth first so that the chains are as short
+// as possible. A true candidate makes everything weakly linked to it false, and a false candidate
+// makes everything strongly linked to it true.
+fn get_implications(fin: &CellCandidate, strong_links: &Links, weak_links: &Links) -> Implications {
+    let mut implications = Implications::new();
+    implications.insert((fin.clone(), true), None);
+
+    let mut to_visit = VecDeque::from([(fin.clone(), true)]);
+
+    while let Some((current, is_on)) = to_visit.pop_front() {
+        let links = if is_on { weak_links } else { strong_links };
+
+        let Some(linked) = links.get(&current) else {
+            continue;
+        };
+
+        for next in linked.iter() {
+            let implication = (next.clone(), !is_on);
+
+            if implications.contains_key(&implication) {
+                continue;
+            }
+
+            implications.insert(implication.clone(), Some((current.clone(), is_on)));
+            to_visit.push_back(implication);
+        }
+    }
+
+    implications
+}
+
+fn make_highlights(
+    body: &[CellCandidate],
+    fins: &[CellCandidate],
+    implications_from: &HashMap<CellCandidate, Implications>,
+    to_eliminate: &[CellCandidate],
+) -> Vec<Highlight> {
+    let mut highlights = vec![];
+
+    for (cell_candidates, cell_color, fg, bg) in [
+        (
+            body,
+            HighlightColor::Orange,
+            HighlightColor::NoteFg,
+            HighlightColor::NoteBg,
+        ),
+        (
+            fins,
+            HighlightColor::Cyan,
+            HighlightColor::NoteSecondaryFg,
+            HighlightColor::NoteSecondaryBg,
+        ),
+    ] {
+        for cell_candidate in cell_candidates.iter() {
+            let (row, col, _) = cell_candidate.as_tuple();
+
+            highlights.push(Highlight::new_cell_hl(row, col, cell_color));
+            highlights.push(Highlight::new_candidate_hl(cell_candidate, fg, bg));
+        }
+    }
+
+    // the chain from each fin to the first eliminated candidate, with weak links dashed
+    for fin in fins.iter() {
+        let implications = &implications_from[fin];
+        let mut current = (to_eliminate[0].clone(), false);
+
+        while let Some(Some(previous)) = implications.get(&current) {
+            highlights.push(Highlight::new_line_hl(
+                &previous.0,
+                &current.0,
+                HighlightColor::Red,
+                previous.1,
+            ));
+
+            current = previous.clone();
+        }
+    }
+
+    for cell_candidate in to_eliminate.iter() {
+        highlights.push(Highlight::new_candidate_hl(
+            cell_candidate,
+            HighlightColor::ElimFg,
+            HighlightColor::ElimBg,
+        ));
+    }
+
+    highlights
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_kraken_fish() {
+        let bd =
+            "000060570320007040700410000030000400106000803004000010000094001010700059098030000";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let expected = vec![CellCandidate::from(2, 5, 3)];
+
+        let kraken_fish = find_kraken_fish(&grid).unwrap();
+        let to_place = kraken_fish.get_to_place().clone();
+        let to_eliminate = kraken_fish.get_to_eliminate().clone();
+
+        assert_eq!("Kraken X-Wing", kraken_fish.get_name());
+        assert_eq!(Vec::<CellCandidate>::new(), to_place);
+        assert_eq!(expected, to_eliminate);
+    }
+
+    #[test]
+    fn test_kraken_fish_skips_finned_fish() {
+        let bd =
+            "137450980486791000025836700304189500851204390209305148792548600618920000543610809";
+        let grid = Grid::from_str(bd).unwrap();
+
+        // r1c9 <> 2 comes from a fish whose fins all see it, which is a plain finned fish
+        let expected = vec![CellCandidate::from(7, 8, 7)];
+
+        let kraken_fish = find_kraken_fish(&grid).unwrap();
+        let to_eliminate = kraken_fish.get_to_eliminate().clone();
+
+        assert_eq!(expected, to_eliminate);
+    }
+}
@@ -1,6 +1,7 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::grid::{CellCandidate, Grid, UnitType};
+use super::als::{get_all_als, get_seen_by_all};
+use crate::grid::{get_minigrid_n_from_coords, CellCandidate, Grid, UnitType};
 
 use UnitType::{Col, MiniGrid, Row};
 
@@ -9,8 +10,15 @@ pub enum LinkType {
     StrongInUnit,
     WeakInCell,
     WeakInUnit,
+    StrongGroupedInUnit,
+    WeakGroupedInUnit,
+    StrongInALS,
+    WeakToALS,
 }
 
+// larger sets make for too many nodes to search through
+const MAX_ALS_SIZE: usize = 3;
+
 #[derive(Eq, PartialEq, Hash, Clone)]
 pub struct LinkNode {
     cell_candidates: Vec<CellCandidate>,
@@ -33,6 +41,16 @@ impl LinkNode {
         &self.cell_candidates
     }
 
+    pub fn is_group(&self) -> bool {
+        self.cell_candidates.len() > 1
+    }
+
+    pub fn overlaps(&self, other: &LinkNode) -> bool {
+        self.cell_candidates
+            .iter()
+            .any(|cell_candidate| other.cell_candidates.contains(cell_candidate))
+    }
+
     pub fn same_cell(&self, other: &LinkNode) -> bool {
         if self.cell_candidates.len() > 1 || other.cell_candidates.len() > 1 {
             return false;
@@ -79,6 +97,10 @@ fn make_from_link(grid: &Grid, link_type: &LinkType) -> LinkMap {
         StrongInUnit => make_in_units(grid, true),
         WeakInCell => make_in_cells(grid, false),
         WeakInUnit => make_in_units(grid, false),
+        StrongGroupedInUnit => make_grouped_in_units(grid, true),
+        WeakGroupedInUnit => make_grouped_in_units(grid, false),
+        StrongInALS => make_in_als(grid),
+        WeakToALS => make_to_als(grid),
     }
 }
 
@@ -143,3 +165,127 @@ fn make_in_units(grid: &Grid, only_strong_links: bool) -> LinkMap {
 
     map
 }
+
+// Links within a unit where at least one end is a group, the candidates of a digit in a box-line
+// intersection. Links between single candidates are left to make_in_units.
+fn make_grouped_in_units(grid: &Grid, only_strong_links: bool) -> LinkMap {
+    let mut map = LinkMap::new();
+
+    for unit_type in &[Row, Col, MiniGrid] {
+        for k in 0..9 {
+            let unit = grid.get_unit(unit_type, k);
+
+            for val in 1..10 {
+                let mut cell_candidates: Vec<CellCandidate> = unit
+                    .scan(val)
+                    .iter()
+                    .map(|cell| CellCandidate::from_cell(cell, val))
+                    .collect();
+                cell_candidates.sort();
+
+                let nodes = get_unit_nodes(unit_type, &cell_candidates);
+
+                for a in nodes.iter() {
+                    for b in nodes.iter() {
+                        if !(a.is_group() || b.is_group()) || a.overlaps(b) {
+                            continue;
+                        }
+
+                        // a strong link needs the two nodes to cover the whole unit
+                        if only_strong_links
+                            && a.get().len() + b.get().len() != cell_candidates.len()
+                        {
+                            continue;
+                        }
+
+                        map.entry(a.clone()).or_default().insert(b.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    map
+}
+
+fn get_unit_nodes(unit_type: &UnitType, cell_candidates: &[CellCandidate]) -> Vec<LinkNode> {
+    let mut nodes: Vec<LinkNode> = cell_candidates
+        .iter()
+        .map(|cell_candidate| LinkNode::from(cell_candidate.clone()))
+        .collect();
+
+    let get_intersections: Vec<fn(&CellCandidate) -> u32> = match unit_type {
+        Row | Col => vec![|cc| get_minigrid_n_from_coords(cc.get_row(), cc.get_col())],
+        MiniGrid => vec![|cc| cc.get_row(), |cc| cc.get_col()],
+    };
+
+    for get_intersection in get_intersections.iter() {
+        for n in 0..9 {
+            let group: Vec<CellCandidate> = cell_candidates
+                .iter()
+                .filter(|cell_candidate| get_intersection(cell_candidate) == n)
+                .cloned()
+                .collect();
+
+            if group.len() > 1 {
+                nodes.push(LinkNode::from_multi(&group));
+            }
+        }
+    }
+
+    nodes
+}
+
+// An almost locked set that loses one of its candidates becomes a locked set, so it has to hold all
+// of the others. If none of its x candidates is true, at least one of its z candidates is, which is
+// a strong link between the two groups. Single cells are left to make_in_cells.
+fn make_in_als(grid: &Grid) -> LinkMap {
+    let mut map = LinkMap::new();
+
+    for als in get_all_als(grid, MAX_ALS_SIZE).iter() {
+        if als.get_cells().len() < 2 {
+            continue;
+        }
+
+        for x in als.get_candidates().iter() {
+            for z in als.get_candidates().iter() {
+                if x == z {
+                    continue;
+                }
+
+                let node_x = LinkNode::from_multi(&als.get_cell_candidates(x));
+                let node_z = LinkNode::from_multi(&als.get_cell_candidates(z));
+
+                map.entry(node_x).or_default().insert(node_z);
+            }
+        }
+    }
+
+    map
+}
+
+// If any of the z candidates of an almost locked set is true, then candidates outside the set that
+// see all of them are false
+fn make_to_als(grid: &Grid) -> LinkMap {
+    let mut map = LinkMap::new();
+
+    for als in get_all_als(grid, MAX_ALS_SIZE).iter() {
+        if als.get_cells().len() < 2 {
+            continue;
+        }
+
+        for z in als.get_candidates().iter() {
+            let cell_candidates = als.get_cell_candidates(z);
+            let node_z = LinkNode::from_multi(&cell_candidates);
+
+            for cell_candidate in get_seen_by_all(grid, &cell_candidates, &[als]) {
+                let node = LinkNode::from(cell_candidate);
+
+                map.entry(node_z.clone()).or_default().insert(node.clone());
+                map.entry(node).or_default().insert(node_z.clone());
+            }
+        }
+    }
+
+    map
+}
@@ -9,19 +9,19 @@ use super::{
 };
 use crate::grid::{get_minigrid_n_from_coords, CellCandidate, Grid};
 
-type CellCoords = (u32, u32);
-type ColorMap = HashMap<CellCandidate, Color>;
-type CellColorMap = HashMap<CellCoords, HashMap<u32, Color>>;
-type InverseColorMap = HashMap<Color, Vec<CellCandidate>>;
+pub(super) type CellCoords = (u32, u32);
+pub(super) type ColorMap = HashMap<CellCandidate, Color>;
+pub(super) type CellColorMap = HashMap<CellCoords, HashMap<u32, Color>>;
+pub(super) type InverseColorMap = HashMap<Color, Vec<CellCandidate>>;
 
 #[derive(Copy, Clone, Eq, PartialEq, Hash)]
-enum Color {
+pub(super) enum Color {
     ColorA,
     ColorB,
 }
 
 impl Color {
-    fn opposite(&self) -> Color {
+    pub(super) fn opposite(&self) -> Color {
         match self {
             ColorA => ColorB,
             ColorB => ColorA,
@@ -75,7 +75,7 @@ pub fn find_medusa(grid: &Grid) -> Option<StrategyResult> {
     None
 }
 
-fn get_component_starts(strong_link_map: &LinkMap) -> Vec<LinkNode> {
+pub(super) fn get_component_starts(strong_link_map: &LinkMap) -> Vec<LinkNode> {
     let mut component_starts = vec![];
 
     let mut visited = HashSet::new();
@@ -113,7 +113,10 @@ fn get_component_starts(strong_link_map: &LinkMap) -> Vec<LinkNode> {
     component_starts
 }
 
-fn color_component(start: &LinkNode, strong_link_map: &LinkMap) -> (ColorMap, CellColorMap) {
+pub(super) fn color_component(
+    start: &LinkNode,
+    strong_link_map: &LinkMap,
+) -> (ColorMap, CellColorMap) {
     let mut color_map = ColorMap::new();
     let mut cell_color_map = CellColorMap::new();
 
@@ -156,7 +159,7 @@ fn color_component(start: &LinkNode, strong_link_map: &LinkMap) -> (ColorMap, Ce
     (color_map, cell_color_map)
 }
 
-fn get_inverse_color_map(color_map: &ColorMap) -> InverseColorMap {
+pub(super) fn get_inverse_color_map(color_map: &ColorMap) -> InverseColorMap {
     let mut inverse_color_map = InverseColorMap::from([(ColorA, vec![]), (ColorB, vec![])]);
 
     for (cell_candidate, color) in color_map.iter() {
@@ -197,11 +200,12 @@ fn twice_in_a_cell(cell_color_map: &CellColorMap, color_map: &ColorMap) -> Optio
 
             let highlights = make_highlights(color_map, Some(&twice_color), None);
 
-            return Some(StrategyResult::from(
+            return Some(StrategyResult::from_chain(
                 "Medusa - Twice in a Cell",
                 to_place,
                 vec![],
                 highlights,
+                color_map.len(),
             ));
         }
     }
@@ -230,11 +234,12 @@ fn twice_in_a_unit(
 
                 let highlights = make_highlights(color_map, Some(color), None);
 
-                return Some(StrategyResult::from(
+                return Some(StrategyResult::from_chain(
                     "Medusa - Twice in a Unit",
                     to_place,
                     vec![],
                     highlights,
+                    color_map.len(),
                 ));
             }
         }
@@ -267,11 +272,12 @@ fn two_colors_in_cell(
     } else {
         let highlights = make_highlights(color_map, None, Some(&to_eliminate));
 
-        Some(StrategyResult::from(
+        Some(StrategyResult::from_chain(
             "Medusa - Two Colors in a Cell",
             vec![],
             to_eliminate,
             highlights,
+            color_map.len(),
         ))
     }
 }
@@ -321,11 +327,12 @@ fn two_colors_elsewhere(grid: &Grid, color_map: &ColorMap) -> Option<StrategyRes
 
         let highlights = make_highlights(color_map, None, Some(&to_eliminate));
 
-        Some(StrategyResult::from(
+        Some(StrategyResult::from_chain(
             "Medusa - Two Colors Elsewhere",
             vec![],
             to_eliminate,
             highlights,
+            color_map.len(),
         ))
     }
 }
@@ -367,11 +374,12 @@ fn two_colors_unit_cell(
     } else {
         let highlights = make_highlights(color_map, None, Some(&to_eliminate));
 
-        Some(StrategyResult::from(
+        Some(StrategyResult::from_chain(
             "Medusa - Two Colors Unit + Cell",
             vec![],
             to_eliminate,
             highlights,
+            color_map.len(),
         ))
     }
 }
@@ -428,11 +436,12 @@ fn cell_emptied_by_color(
                     HighlightColor::Cyan,
                 ));
 
-                return Some(StrategyResult::from(
+                return Some(StrategyResult::from_chain(
                     "Medusa - Cell Emptied by Color",
                     to_place,
                     vec![],
                     highlights,
+                    color_map.len(),
                 ));
             }
         }
@@ -441,7 +450,7 @@ fn cell_emptied_by_color(
     None
 }
 
-fn make_highlights(
+pub(super) fn make_highlights(
     color_map: &ColorMap,
     elim_color: Option<&Color>,
     to_eliminate: Option<&Vec<CellCandidate>>,
@@ -1,36 +1,68 @@
+mod aligned_exclusion;
+mod avoidable_rectangle;
+mod bug;
+mod chain;
+mod coloring;
+mod exocet;
+mod fish;
+mod forcing;
 mod hidden_set;
 mod hidden_single;
+mod kraken;
 mod locked_candidates;
 mod medusa;
+mod multi_fish;
 mod naked_set;
 mod naked_single;
+mod pattern_overlay;
 mod pointing_set;
 mod rectangle_elimination;
+mod remote_pairs;
 mod single_digit_pattern;
+mod sk_loop;
 mod strategy_result;
 mod strategy_type;
-mod swordfish;
-mod xwing;
+mod sue_de_coq;
+mod unique_rectangle;
+mod wwing;
+mod wxyzwing;
 mod xywing;
 mod xyzwing;
 
 pub mod aic;
+pub mod als;
 pub mod highlight;
 pub mod link;
 
-pub use aic::find_general_aic;
+pub use aic::{find_aic_with_als, find_general_aic};
+pub use aligned_exclusion::*;
+pub use als::{find_als_xy_wing, find_als_xz, find_death_blossom};
+pub use avoidable_rectangle::*;
+pub use bug::*;
+pub use chain::*;
+pub use coloring::*;
+pub use exocet::*;
+pub use fish::*;
+pub use forcing::*;
 pub use hidden_set::*;
 pub use hidden_single::*;
+pub use kraken::*;
 pub use locked_candidates::*;
-pub use medusa::*;
+pub use medusa::find_medusa;
+pub use multi_fish::*;
 pub use naked_set::*;
 pub use naked_single::*;
+pub use pattern_overlay::*;
 pub use pointing_set::*;
 pub use rectangle_elimination::*;
+pub use remote_pairs::*;
 pub use single_digit_pattern::*;
+pub use sk_loop::*;
 pub use strategy_result::*;
 pub use strategy_type::*;
-pub use swordfish::*;
-pub use xwing::*;
+pub use sue_de_coq::*;
+pub use unique_rectangle::*;
+pub use wwing::*;
+pub use wxyzwing::*;
 pub use xywing::*;
 pub use xyzwing::*;
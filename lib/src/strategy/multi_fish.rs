@@ -0,0 +1,342 @@
+use itertools::Itertools;
+
+use super::{
+    highlight::{Highlight, HighlightColor},
+    StrategyResult,
+};
+use crate::grid::{CellCandidate, Grid};
+
+// Multi-Fish: a fish over several digits at once. Its base is made of a few rows (or columns) and
+// a few digits, each digit appearing exactly once in each of the rows. That's one true candidate
+// for every row and digit. The cover is made of as many sets, each holding at most one true
+// candidate: columns for a single digit (or rows, when the base is made of columns) and cells.
+//
+// When the cover sets hold all of the base candidates, each of them holds exactly one of the true
+// ones, and that candidate is in no other cover set. So the rest of each cover set is false, and so
+// are the base candidates in two cover sets. Like the SK-Loop, this counting works the same way
+// however the sets are arranged.
+pub fn find_multi_fish(grid: &Grid) -> Option<StrategyResult> {
+    for (n_lines, n_vals) in [(2, 2), (2, 3), (3, 2), (3, 3)] {
+        // a cell holds at most one base candidate for each digit and a cross line one for each
+        // line, which rules out most bases before looking for a cover
+        let max_candidates = (n_lines * n_vals * n_lines.max(n_vals)) as u32;
+
+        for is_transposed in [false, true] {
+            let counts = get_counts(grid, is_transposed);
+
+            for lines in (0..9).combinations(n_lines) {
+                for vals in (1..10).combinations(n_vals) {
+                    let base_counts = lines
+                        .iter()
+                        .cartesian_product(vals.iter())
+                        .map(|(&line, &val)| counts[line as usize][val as usize]);
+
+                    // val being placed in the line leaves it without candidates
+                    if base_counts.clone().any(|count| count == 0)
+                        || base_counts.sum::<u32>() > max_candidates
+                    {
+                        continue;
+                    }
+
+                    let search = MultiFishSearch::from(grid, is_transposed, &lines, &vals);
+
+                    if let Some(result) = search.find() {
+                        return Some(result);
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
+// the number of candidates for each digit in each row, or in each column when transposed
+fn get_counts(grid: &Grid, is_transposed: bool) -> [[u32; 10]; 9] {
+    let mut counts = [[0; 10]; 9];
+
+    for row in 0..9 {
+        for col in 0..9 {
+            if grid.get_placed(row, col) != 0 {
+                continue;
+            }
+
+            let line = if is_transposed { col } else { row };
+
+            for val in grid.get_candidates(row, col).iter() {
+                counts[line as usize][val as usize] += 1;
+            }
+        }
+    }
+
+    counts
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Cover {
+    Cell(u32, u32),
+    Cross(u32, u32),
+}
+
+struct MultiFishSearch<'a> {
+    grid: &'a Grid,
+    is_transposed: bool,
+    lines: &'a [u32],
+    vals: &'a [u32],
+    // the base candidates, as the line, the position in the line and the value
+    base: Vec<(u32, u32, u32)>,
+    // a mask of the base candidates for each row (or column) and digit of the base
+    truths: Vec<u128>,
+}
+
+impl<'a> MultiFishSearch<'a> {
+    fn from(
+        grid: &'a Grid,
+        is_transposed: bool,
+        lines: &'a [u32],
+        vals: &'a [u32],
+    ) -> MultiFishSearch<'a> {
+        let mut search = MultiFishSearch {
+            grid,
+            is_transposed,
+            lines,
+            vals,
+            base: vec![],
+            truths: vec![],
+        };
+
+        for &line in lines.iter() {
+            for &val in vals.iter() {
+                let mut truth = 0;
+
+                for pos in 0..9 {
+                    if search.has_candidate(line, pos, val) {
+                        truth |= 1 << search.base.len();
+                        search.base.push((line, pos, val));
+                    }
+                }
+
+                search.truths.push(truth);
+            }
+        }
+
+        search
+    }
+
+    // rows and columns swap places when the base is made of columns
+    fn get_coords(&self, line: u32, pos: u32) -> (u32, u32) {
+        if self.is_transposed {
+            (pos, line)
+        } else {
+            (line, pos)
+        }
+    }
+
+    fn has_candidate(&self, line: u32, pos: u32, val: u32) -> bool {
+        let (row, col) = self.get_coords(line, pos);
+
+        self.grid.get_placed(row, col) == 0 && self.grid.get_candidates(row, col).contains(val)
+    }
+
+    fn get_mask(&self, cover: Cover) -> u128 {
+        self.base
+            .iter()
+            .enumerate()
+            .filter(|(_, &(line, pos, val))| match cover {
+                Cover::Cell(cover_line, cover_pos) => line == cover_line && pos == cover_pos,
+                Cover::Cross(cover_pos, cover_val) => pos == cover_pos && val == cover_val,
+            })
+            .fold(0, |mask, (i, _)| mask | (1 << i))
+    }
+
+    fn find(&self) -> Option<StrategyResult> {
+        self.find_cover(&mut vec![], 0)
+    }
+
+    // The first uncovered base candidate has to be covered by either its cell or its cross line
+    // for its digit, so the cover is built up one of those at a time.
+    fn find_cover(&self, cover: &mut Vec<Cover>, cover_mask: u128) -> Option<StrategyResult> {
+        let all = (1u128 << self.base.len()) - 1;
+        let uncovered = all & !cover_mask;
+
+        if uncovered == 0 {
+            return self.check(cover);
+        }
+
+        let remaining = (self.truths.len() - cover.len()) as u32;
+        let max_per_cover = self.lines.len().max(self.vals.len()) as u32;
+
+        if uncovered.count_ones() > remaining * max_per_cover {
+            return None;
+        }
+
+        let (line, pos, val) = self.base[uncovered.trailing_zeros() as usize];
+
+        for next in [Cover::Cell(line, pos), Cover::Cross(pos, val)] {
+            cover.push(next);
+            let result = self.find_cover(cover, cover_mask | self.get_mask(next));
+            cover.pop();
+
+            if result.is_some() {
+                return result;
+            }
+        }
+
+        None
+    }
+
+    fn check(&self, cover: &[Cover]) -> Option<StrategyResult> {
+        if cover.len() != self.truths.len() || self.splits(cover) {
+            return None;
+        }
+
+        let mut to_eliminate = vec![];
+
+        for &next in cover.iter() {
+            match next {
+                Cover::Cell(line, pos) => {
+                    let (row, col) = self.get_coords(line, pos);
+
+                    for val in self.grid.get_candidates(row, col).iter() {
+                        if !self.vals.contains(&val) {
+                            to_eliminate.push(CellCandidate::from(row, col, val));
+                        }
+                    }
+                }
+                Cover::Cross(pos, val) => {
+                    for line in (0..9).filter(|line| !self.lines.contains(line)) {
+                        if self.has_candidate(line, pos, val) {
+                            let (row, col) = self.get_coords(line, pos);
+
+                            to_eliminate.push(CellCandidate::from(row, col, val));
+                        }
+                    }
+                }
+            }
+        }
+
+        for (i, &(line, pos, val)) in self.base.iter().enumerate() {
+            let count = cover
+                .iter()
+                .filter(|&&next| self.get_mask(next) & (1 << i) != 0)
+                .count();
+
+            if count > 1 {
+                let (row, col) = self.get_coords(line, pos);
+
+                to_eliminate.push(CellCandidate::from(row, col, val));
+            }
+        }
+
+        if to_eliminate.is_empty() {
+            return None;
+        }
+
+        to_eliminate.sort();
+        to_eliminate.dedup();
+
+        let highlights = self.make_highlights(cover, &to_eliminate);
+
+        Some(StrategyResult::from(
+            &self.get_name(),
+            vec![],
+            to_eliminate,
+            highlights,
+        ))
+    }
+
+    // Whether some of the rows and digits of the base are covered by as many of the cover sets,
+    // making a smaller pattern of their own, such as a fish or a hidden set.
+    fn splits(&self, cover: &[Cover]) -> bool {
+        let masks: Vec<u128> = cover.iter().map(|&next| self.get_mask(next)).collect();
+
+        (1..self.truths.len()).any(|size| {
+            self.truths.iter().combinations(size).any(|truths| {
+                let mask = truths.iter().fold(0, |mask, &&truth| mask | truth);
+
+                masks
+                    .iter()
+                    .filter(|&&cover_mask| cover_mask & mask != 0)
+                    .count()
+                    <= size
+            })
+        })
+    }
+
+    // r1 r5 for the first and fifth rows, followed by the digits
+    fn get_name(&self) -> String {
+        let prefix = if self.is_transposed { "c" } else { "r" };
+
+        let lines = self
+            .lines
+            .iter()
+            .map(|line| format!("{}{}", prefix, line + 1))
+            .join(" ");
+
+        format!("Multi-Fish ({} / {})", lines, self.vals.iter().join(" "))
+    }
+
+    fn make_highlights(&self, cover: &[Cover], to_eliminate: &[CellCandidate]) -> Vec<Highlight> {
+        let mut highlights = vec![];
+
+        for &next in cover.iter() {
+            if let Cover::Cell(line, pos) = next {
+                let (row, col) = self.get_coords(line, pos);
+
+                highlights.push(Highlight::new_cell_hl(row, col, HighlightColor::Cyan));
+            }
+        }
+
+        for &(line, pos, val) in self.base.iter() {
+            let (row, col) = self.get_coords(line, pos);
+            let cell_candidate = CellCandidate::from(row, col, val);
+
+            if to_eliminate.contains(&cell_candidate) {
+                continue;
+            }
+
+            highlights.push(Highlight::new_candidate_hl(
+                &cell_candidate,
+                HighlightColor::NoteFg,
+                HighlightColor::NoteBg,
+            ));
+        }
+
+        for cell_candidate in to_eliminate.iter() {
+            highlights.push(Highlight::new_candidate_hl(
+                cell_candidate,
+                HighlightColor::ElimFg,
+                HighlightColor::ElimBg,
+            ));
+        }
+
+        highlights
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_multi_fish() {
+        let bd =
+            "904005000010900340083002009735080920000293000090050831300500090076009513059300700";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let expected = vec![
+            CellCandidate::from(0, 8, 2),
+            CellCandidate::from(4, 0, 4),
+            CellCandidate::from(4, 8, 4),
+            CellCandidate::from(6, 6, 6),
+        ];
+
+        let multi_fish = find_multi_fish(&grid).unwrap();
+        let to_place = multi_fish.get_to_place().clone();
+        let to_eliminate = multi_fish.get_to_eliminate().clone();
+
+        assert_eq!("Multi-Fish (c2 c7 / 2 4)", multi_fish.get_name());
+        assert_eq!(Vec::<CellCandidate>::new(), to_place);
+        assert_eq!(expected, to_eliminate);
+    }
+}
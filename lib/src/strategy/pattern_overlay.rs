@@ -0,0 +1,189 @@
+use super::{
+    fish::get_cell_candidates,
+    highlight::{Highlight, HighlightColor},
+    StrategyResult,
+};
+use crate::grid::{CellCandidate, Grid};
+
+// Pattern Overlay Method: a template is one way of placing val in every row, column and minigrid,
+// using only the cells where val is placed or still a candidate. The solution has to follow one
+// of the templates, so candidates in no template are false and cells in every template are val.
+// Cells are represented as masks with bit 9 * row + col set, as in fish.rs.
+pub fn find_pattern_overlay(grid: &Grid) -> Option<StrategyResult> {
+    for val in 1..10 {
+        let (placed, candidates) = get_masks(grid, val);
+
+        let mut overlay = Overlay {
+            placed,
+            candidates,
+            union: 0,
+            intersection: u128::MAX,
+        };
+
+        overlay.add_templates(0, 0, 0, 0);
+
+        // no templates means the grid is broken, which isn't for this strategy to report
+        if overlay.union == 0 {
+            continue;
+        }
+
+        let to_place = get_cell_candidates(overlay.intersection & !placed, val);
+        let to_eliminate = get_cell_candidates(candidates & !overlay.union, val);
+
+        if to_place.is_empty() && to_eliminate.is_empty() {
+            continue;
+        }
+
+        let highlights = make_highlights(&overlay, val, &to_place, &to_eliminate);
+
+        return Some(StrategyResult::from(
+            "Pattern Overlay",
+            to_place,
+            to_eliminate,
+            highlights,
+        ));
+    }
+
+    None
+}
+
+struct Overlay {
+    placed: u128,
+    candidates: u128,
+    // cells used by any template, and cells used by all of them
+    union: u128,
+    intersection: u128,
+}
+
+impl Overlay {
+    // templates are built a row at a time, keeping track of the columns and minigrids used so far
+    fn add_templates(&mut self, row: u32, used_cols: u16, used_minigrids: u16, template: u128) {
+        if row == 9 {
+            self.union |= template;
+            self.intersection &= template;
+            return;
+        }
+
+        for col in 0..9 {
+            let bit = 1 << (9 * row + col);
+            let minigrid = (row / 3) * 3 + col / 3;
+
+            if (self.placed | self.candidates) & bit == 0
+                || used_cols & (1 << col) != 0
+                || used_minigrids & (1 << minigrid) != 0
+            {
+                continue;
+            }
+
+            // a row where val is placed can only use that cell
+            if self.placed & row_mask(row) != 0 && self.placed & bit == 0 {
+                continue;
+            }
+
+            self.add_templates(
+                row + 1,
+                used_cols | (1 << col),
+                used_minigrids | (1 << minigrid),
+                template | bit,
+            );
+        }
+    }
+}
+
+fn row_mask(row: u32) -> u128 {
+    0x1ff << (9 * row)
+}
+
+fn get_masks(grid: &Grid, val: u32) -> (u128, u128) {
+    let mut placed = 0;
+    let mut candidates = 0;
+
+    for row in 0..9 {
+        for col in 0..9 {
+            let bit = 1 << (9 * row + col);
+
+            if grid.get_placed(row, col) == val {
+                placed |= bit;
+            } else if grid.get_candidates(row, col).contains(val) {
+                candidates |= bit;
+            }
+        }
+    }
+
+    (placed, candidates)
+}
+
+fn make_highlights(
+    overlay: &Overlay,
+    val: u32,
+    to_place: &[CellCandidate],
+    to_eliminate: &[CellCandidate],
+) -> Vec<Highlight> {
+    let mut highlights = vec![];
+
+    let possible = overlay.candidates & overlay.union & !overlay.intersection;
+
+    for cell_candidate in get_cell_candidates(possible, val).iter() {
+        highlights.push(Highlight::new_candidate_hl(
+            cell_candidate,
+            HighlightColor::NoteSecondaryFg,
+            HighlightColor::NoteSecondaryBg,
+        ));
+    }
+
+    for cell_candidate in to_place.iter() {
+        highlights.push(Highlight::new_candidate_hl(
+            cell_candidate,
+            HighlightColor::NoteFg,
+            HighlightColor::NoteBg,
+        ));
+    }
+
+    for cell_candidate in to_eliminate.iter() {
+        highlights.push(Highlight::new_candidate_hl(
+            cell_candidate,
+            HighlightColor::ElimFg,
+            HighlightColor::ElimBg,
+        ));
+    }
+
+    highlights
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pattern_overlay_eliminate() {
+        let bd =
+            "004005000010900340080002009705080020000203000090050801300500090076009010000300700";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let expected = vec![CellCandidate::from(4, 4, 1)];
+
+        let pattern_overlay = find_pattern_overlay(&grid).unwrap();
+        let to_place = pattern_overlay.get_to_place().clone();
+        let to_eliminate = pattern_overlay.get_to_eliminate().clone();
+
+        assert_eq!(Vec::<CellCandidate>::new(), to_place);
+        assert_eq!(expected, to_eliminate);
+    }
+
+    #[test]
+    fn test_pattern_overlay_place() {
+        let bd =
+            "000060570320007040700410000030000400106000803004000010000094001010700059098030000";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let expected_to_place = vec![CellCandidate::from(0, 2, 1), CellCandidate::from(1, 6, 1)];
+        let expected_to_eliminate = vec![CellCandidate::from(1, 2, 1)];
+
+        let pattern_overlay = find_pattern_overlay(&grid).unwrap();
+        let to_place = pattern_overlay.get_to_place().clone();
+        let to_eliminate = pattern_overlay.get_to_eliminate().clone();
+
+        assert_eq!(expected_to_place, to_place);
+        assert_eq!(expected_to_eliminate, to_eliminate);
+    }
+}
@@ -0,0 +1,187 @@
+use std::collections::{HashSet, VecDeque};
+
+use super::{
+    highlight::{Highlight, HighlightColor},
+    StrategyResult,
+};
+use crate::grid::{Cell, CellCandidate, Grid};
+use crate::util::BitSet;
+
+type Component = Vec<(Cell, bool)>;
+type Links = Vec<(Cell, Cell)>;
+
+// A remote pair is a chain of bivalue cells with the same two candidates, each seeing the next.
+// Going along the chain the cells have to alternate between the two candidates, so any two cells
+// an odd number of links apart hold one candidate each. Cells seeing both of them can't have
+// either candidate.
+pub fn find_remote_pairs(grid: &Grid) -> Option<StrategyResult> {
+    let mut bivalue_cells: Vec<Cell> = grid.get_nvalue_cells(2).iter().cloned().collect();
+    bivalue_cells.sort_by_key(|cell| (cell.get_row(), cell.get_col()));
+
+    let mut visited = HashSet::new();
+
+    for start in bivalue_cells.iter() {
+        if visited.contains(start) {
+            continue;
+        }
+
+        let Some((component, links)) = get_component(start, &bivalue_cells) else {
+            continue;
+        };
+
+        for (cell, _) in component.iter() {
+            visited.insert(cell.clone());
+        }
+
+        // shorter chains are naked pairs
+        if component.len() < 4 {
+            continue;
+        }
+
+        let pair = start.get_candidates();
+
+        let to_eliminate: Vec<CellCandidate> = grid
+            .as_region()
+            .iter()
+            .filter(|cell| component.iter().all(|(other, _)| other != *cell))
+            .filter(|cell| sees_both_parities(cell, &component))
+            .flat_map(|cell| {
+                cell.get_candidates()
+                    .intersection(pair)
+                    .iter()
+                    .map(|val| CellCandidate::from_cell(cell, val))
+                    .collect::<Vec<CellCandidate>>()
+            })
+            .collect();
+
+        if to_eliminate.is_empty() {
+            continue;
+        }
+
+        let highlights = make_highlights(&component, &links, pair, &to_eliminate);
+
+        return Some(StrategyResult::from(
+            "Remote Pairs",
+            vec![],
+            to_eliminate,
+            highlights,
+        ));
+    }
+
+    None
+}
+
+// The cells connected to start through cells with the same candidates, along with their parity
+// and the links used to reach them. None if the parities contradict each other.
+fn get_component(start: &Cell, cells: &[Cell]) -> Option<(Component, Links)> {
+    let mut component = vec![(start.clone(), false)];
+    let mut links = vec![];
+
+    let mut to_visit = VecDeque::from([(start.clone(), false)]);
+
+    while let Some((current, parity)) = to_visit.pop_front() {
+        for next in cells.iter() {
+            if next == &current
+                || next.get_candidates() != current.get_candidates()
+                || !next.can_see(&current)
+            {
+                continue;
+            }
+
+            match component.iter().find(|(cell, _)| cell == next) {
+                Some((_, next_parity)) => {
+                    if *next_parity == parity {
+                        return None;
+                    }
+                }
+                None => {
+                    component.push((next.clone(), !parity));
+                    links.push((current.clone(), next.clone()));
+                    to_visit.push_back((next.clone(), !parity));
+                }
+            }
+        }
+    }
+
+    Some((component, links))
+}
+
+fn sees_both_parities(cell: &Cell, component: &[(Cell, bool)]) -> bool {
+    [false, true].iter().all(|&parity| {
+        component
+            .iter()
+            .any(|(other, other_parity)| *other_parity == parity && cell.can_see(other))
+    })
+}
+
+fn make_highlights(
+    component: &[(Cell, bool)],
+    links: &[(Cell, Cell)],
+    pair: &BitSet,
+    to_eliminate: &[CellCandidate],
+) -> Vec<Highlight> {
+    let mut highlights = vec![];
+
+    let first = pair.iter().next().unwrap();
+
+    for (cell, parity) in component.iter() {
+        for val in pair.iter() {
+            let (fg, bg) = if (val == first) != *parity {
+                (HighlightColor::NoteFg, HighlightColor::NoteBg)
+            } else {
+                (
+                    HighlightColor::NoteSecondaryFg,
+                    HighlightColor::NoteSecondaryBg,
+                )
+            };
+
+            highlights.push(Highlight::new_candidate_hl(
+                &CellCandidate::from_cell(cell, val),
+                fg,
+                bg,
+            ));
+        }
+    }
+
+    for (start, end) in links.iter() {
+        highlights.push(Highlight::new_line_hl(
+            &CellCandidate::from_cell(start, first),
+            &CellCandidate::from_cell(end, first),
+            HighlightColor::Red,
+            false,
+        ));
+    }
+
+    for cell_candidate in to_eliminate.iter() {
+        highlights.push(Highlight::new_candidate_hl(
+            cell_candidate,
+            HighlightColor::ElimFg,
+            HighlightColor::ElimBg,
+        ));
+    }
+
+    highlights
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_remote_pairs() {
+        let bd =
+            "000000530015803760900075140390500070650007080872409051000918025509724810128356497";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let expected = vec![CellCandidate::from(3, 6, 6), CellCandidate::from(4, 6, 3)];
+
+        let remote_pairs = find_remote_pairs(&grid).unwrap();
+        let to_place = remote_pairs.get_to_place().clone();
+        let mut to_eliminate = remote_pairs.get_to_eliminate().clone();
+
+        to_eliminate.sort();
+
+        assert_eq!(Vec::<CellCandidate>::new(), to_place);
+        assert_eq!(expected, to_eliminate);
+    }
+}
@@ -0,0 +1,264 @@
+use itertools::Itertools;
+
+use super::{
+    highlight::{Highlight, HighlightColor},
+    StrategyResult,
+};
+use crate::grid::{get_minigrid_n_from_coords, Cell, CellCandidate, Grid, Region, UnitType};
+use crate::util::BitSet;
+
+use UnitType::{Col, MiniGrid, Row};
+
+// SK-Loop: two rows and two columns in different bands and stacks cross in four minigrids. In
+// each of them the crossing row and column, without the cell they share, leave a pair of cells in
+// the row and a pair in the column, making 16 cells in 8 pairs. Going around the loop, each pair
+// shares a unit with the next: a row or column with the pair in the next minigrid, or its minigrid
+// with the other pair there.
+//
+// Each pair is filled by two of the digits linking it to its neighbours, and the digits linking two
+// pairs appear at most once across them. So if the links hold 16 digits between them, every link
+// digit is in one of its two pairs and can be eliminated from the rest of the unit they share.
+//
+// The same counting over rows or columns and several digits is left to multi_fish.rs.
+pub fn find_sk_loop(grid: &Grid) -> Option<StrategyResult> {
+    for (r1, r2) in (0..9).tuple_combinations::<(_, _)>() {
+        if r1 / 3 == r2 / 3 {
+            continue;
+        }
+
+        for (c1, c2) in (0..9).tuple_combinations::<(_, _)>() {
+            if c1 / 3 == c2 / 3 {
+                continue;
+            }
+
+            let Some(pairs) = get_pairs(grid, (r1, r2), (c1, c2)) else {
+                continue;
+            };
+
+            let units = [
+                (Row, r1),
+                (MiniGrid, get_minigrid_n_from_coords(r1, c2)),
+                (Col, c2),
+                (MiniGrid, get_minigrid_n_from_coords(r2, c2)),
+                (Row, r2),
+                (MiniGrid, get_minigrid_n_from_coords(r2, c1)),
+                (Col, c1),
+                (MiniGrid, get_minigrid_n_from_coords(r1, c1)),
+            ];
+
+            let Some(links) = get_links(&pairs) else {
+                continue;
+            };
+
+            let mut to_eliminate = vec![];
+
+            for (i, (unit_type, n)) in units.iter().enumerate() {
+                let loop_cells =
+                    Region::from(&[pairs[i].clone(), pairs[(i + 1) % 8].clone()].concat());
+
+                for cell in grid.get_unit(unit_type, *n).difference(&loop_cells).iter() {
+                    for val in cell.get_candidates().intersection(&links[i]).iter() {
+                        to_eliminate.push(CellCandidate::from_cell(cell, val));
+                    }
+                }
+            }
+
+            to_eliminate.sort();
+            to_eliminate.dedup();
+
+            if to_eliminate.is_empty() {
+                continue;
+            }
+
+            let highlights = make_highlights(&pairs, &links, &to_eliminate);
+
+            return Some(StrategyResult::from(
+                "SK-Loop",
+                vec![],
+                to_eliminate,
+                highlights,
+            ));
+        }
+    }
+
+    None
+}
+
+// The pairs in loop order, starting with the row pair in the minigrid of r1 and c1 and heading
+// along r1. None if any of the cells is solved.
+fn get_pairs(grid: &Grid, (r1, r2): (u32, u32), (c1, c2): (u32, u32)) -> Option<Vec<Vec<Cell>>> {
+    // the cells of the minigrid's row or column other than the one in the crossing line
+    let others = |n: u32| (n / 3 * 3..n / 3 * 3 + 3).filter(move |&k| k != n);
+
+    let row_pair = |row: u32, col: u32| others(col).map(move |c| (row, c)).collect::<Vec<_>>();
+    let col_pair = |row: u32, col: u32| others(row).map(move |r| (r, col)).collect::<Vec<_>>();
+
+    let coords = [
+        row_pair(r1, c1),
+        row_pair(r1, c2),
+        col_pair(r1, c2),
+        col_pair(r2, c2),
+        row_pair(r2, c2),
+        row_pair(r2, c1),
+        col_pair(r2, c1),
+        col_pair(r1, c1),
+    ];
+
+    let mut pairs = vec![];
+
+    for pair_coords in coords.iter() {
+        if pair_coords.iter().any(|&(r, c)| grid.get_placed(r, c) != 0) {
+            return None;
+        }
+
+        pairs.push(
+            pair_coords
+                .iter()
+                .map(|&(r, c)| Cell::from(r, c, grid.get_candidates(r, c)))
+                .collect(),
+        );
+    }
+
+    Some(pairs)
+}
+
+fn get_candidates(pair: &[Cell]) -> BitSet {
+    pair[0].get_candidates().union(pair[1].get_candidates())
+}
+
+// The digits linking each pair to the next. Every candidate of a pair has to link it to exactly
+// one of its neighbours, so the links of each digit can be worked out on their own. Between them
+// the pairs hold 32 candidates, making the 16 links needed.
+fn get_links(pairs: &[Vec<Cell>]) -> Option<Vec<BitSet>> {
+    let candidates: Vec<BitSet> = pairs.iter().map(|pair| get_candidates(pair)).collect();
+
+    if candidates.iter().map(|vals| vals.len()).sum::<u32>() != 32 {
+        return None;
+    }
+
+    let mut links = vec![BitSet::new(); 8];
+
+    for val in 1..10 {
+        // the pairs linked onwards with val, as bit i for pair i and pair i + 1
+        let shared: u32 = (0..8)
+            .filter(|&i| candidates[i].contains(val) && candidates[(i + 1) % 8].contains(val))
+            .fold(0, |mask, i| mask | (1 << i));
+
+        let is_linked = |mask: u32, i: usize| mask & (1 << i) != 0;
+
+        let mask = (0..256).find(|&mask| {
+            mask & !shared == 0
+                && (0..8).all(|i| {
+                    !candidates[i].contains(val)
+                        || is_linked(mask, (i + 7) % 8) != is_linked(mask, i)
+                })
+        })?;
+
+        for (i, link) in links.iter_mut().enumerate() {
+            if is_linked(mask, i) {
+                link.insert(val);
+            }
+        }
+    }
+
+    Some(links)
+}
+
+fn make_highlights(
+    pairs: &[Vec<Cell>],
+    links: &[BitSet],
+    to_eliminate: &[CellCandidate],
+) -> Vec<Highlight> {
+    let mut highlights = vec![];
+
+    for pair in pairs.iter() {
+        for cell in pair.iter() {
+            highlights.push(Highlight::new_cell_hl(
+                cell.get_row(),
+                cell.get_col(),
+                HighlightColor::Orange,
+            ));
+        }
+    }
+
+    for (i, link) in links.iter().enumerate() {
+        let (fg, bg) = if i % 2 == 0 {
+            (HighlightColor::NoteFg, HighlightColor::NoteBg)
+        } else {
+            (
+                HighlightColor::NoteSecondaryFg,
+                HighlightColor::NoteSecondaryBg,
+            )
+        };
+
+        for val in link.iter() {
+            let ends: Vec<Vec<CellCandidate>> = [&pairs[i], &pairs[(i + 1) % 8]]
+                .iter()
+                .map(|pair| {
+                    pair.iter()
+                        .filter(|cell| cell.get_candidates().contains(val))
+                        .map(|cell| CellCandidate::from_cell(cell, val))
+                        .collect()
+                })
+                .collect();
+
+            for cell_candidate in ends.iter().flatten() {
+                highlights.push(Highlight::new_candidate_hl(cell_candidate, fg, bg));
+            }
+
+            highlights.push(Highlight::new_line_hl(
+                &ends[0][0],
+                &ends[1][0],
+                HighlightColor::Red,
+                false,
+            ));
+        }
+    }
+
+    for cell_candidate in to_eliminate.iter() {
+        highlights.push(Highlight::new_candidate_hl(
+            cell_candidate,
+            HighlightColor::ElimFg,
+            HighlightColor::ElimBg,
+        ));
+    }
+
+    highlights
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_sk_loop() {
+        // Easter Monster
+        let bd =
+            "100000002090400050006000700050903000000070000000850040700000600030009080002000001";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let expected = vec![
+            CellCandidate::from(0, 2, 7),
+            CellCandidate::from(1, 4, 3),
+            CellCandidate::from(1, 4, 8),
+            CellCandidate::from(1, 5, 8),
+            CellCandidate::from(2, 0, 2),
+            CellCandidate::from(4, 1, 4),
+            CellCandidate::from(4, 1, 8),
+            CellCandidate::from(4, 7, 3),
+            CellCandidate::from(4, 7, 9),
+            CellCandidate::from(6, 2, 1),
+            CellCandidate::from(7, 3, 5),
+            CellCandidate::from(7, 4, 4),
+            CellCandidate::from(8, 0, 6),
+        ];
+
+        let sk_loop = find_sk_loop(&grid).unwrap();
+        let to_place = sk_loop.get_to_place().clone();
+        let to_eliminate = sk_loop.get_to_eliminate().clone();
+
+        assert_eq!("SK-Loop", sk_loop.get_name());
+        assert_eq!(Vec::<CellCandidate>::new(), to_place);
+        assert_eq!(expected, to_eliminate);
+    }
+}
@@ -9,6 +9,7 @@ pub struct StrategyResult {
     to_place: Vec<CellCandidate>,
     to_eliminate: Vec<CellCandidate>,
     highlights: Vec<Highlight>,
+    chain_length: Option<usize>,
 }
 
 impl StrategyResult {
@@ -23,6 +24,23 @@ impl StrategyResult {
             to_place,
             to_eliminate,
             highlights,
+            chain_length: None,
+        }
+    }
+
+    pub fn from_chain(
+        name: &str,
+        to_place: Vec<CellCandidate>,
+        to_eliminate: Vec<CellCandidate>,
+        highlights: Vec<Highlight>,
+        chain_length: usize,
+    ) -> StrategyResult {
+        StrategyResult {
+            name: name.to_string(),
+            to_place,
+            to_eliminate,
+            highlights,
+            chain_length: Some(chain_length),
         }
     }
 
@@ -37,4 +55,8 @@ impl StrategyResult {
     pub fn get_to_eliminate(&self) -> &Vec<CellCandidate> {
         &self.to_eliminate
     }
+
+    pub fn get_chain_length(&self) -> Option<usize> {
+        self.chain_length
+    }
 }
@@ -1,7 +1,7 @@
 use super::*;
 use crate::grid::Grid;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Strategy {
     NakedSingle,
     HiddenSingle,
@@ -12,11 +12,58 @@ pub enum Strategy {
     XWing,
     XYWing,
     XYZWing,
+    RemotePairs,
+    WWing,
+    WXYZWing,
     Swordfish,
+    Jellyfish,
     RectangleElimination,
     SingleDigitPattern,
+    SimpleColoring,
+    MultiColoring,
+    FinnedFish,
+    UniqueRectangle,
+    AvoidableRectangle,
+    BUG,
+    XChain,
+    XYChain,
+    AlignedPairExclusion,
     Medusa,
+    FrankenFish,
+    MutantFish,
+    KrakenFish,
+    PatternOverlay,
+    AlignedTripleExclusion,
+    SueDeCoq,
+    ALSXZ,
+    ALSXYWing,
+    DeathBlossom,
     AIC,
+    AICWithALS,
+    MultiFish,
+    ForcingChain,
+    JuniorExocet,
+    SKLoop,
+}
+
+// tiers matching the files of the sudoku-exchange-puzzle-bank
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+    Diabolical,
+}
+
+impl Difficulty {
+    pub fn to_str(&self) -> &str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Medium => "Medium",
+            Difficulty::Hard => "Hard",
+            Difficulty::Diabolical => "Diabolical",
+        }
+    }
 }
 
 use Strategy::*;
@@ -24,20 +71,47 @@ use Strategy::*;
 impl Strategy {
     pub fn get_all() -> Vec<Strategy> {
         vec![
-            NakedSingle,
             HiddenSingle,
+            NakedSingle,
             PointingSet,
             LockedCandidates,
             NakedSet,
             HiddenSet,
             XWing,
+            Swordfish,
+            FinnedFish,
             XYWing,
+            RemotePairs,
             XYZWing,
-            Swordfish,
+            WWing,
             RectangleElimination,
             SingleDigitPattern,
+            SimpleColoring,
+            UniqueRectangle,
+            AvoidableRectangle,
+            WXYZWing,
+            MultiColoring,
+            Jellyfish,
+            BUG,
             Medusa,
+            XChain,
+            XYChain,
+            AlignedPairExclusion,
+            FrankenFish,
             AIC,
+            MutantFish,
+            SueDeCoq,
+            KrakenFish,
+            PatternOverlay,
+            AlignedTripleExclusion,
+            ALSXZ,
+            ALSXYWing,
+            DeathBlossom,
+            AICWithALS,
+            MultiFish,
+            ForcingChain,
+            SKLoop,
+            JuniorExocet,
         ]
     }
 
@@ -52,11 +126,114 @@ impl Strategy {
             XWing => find_xwing,
             XYWing => find_xywing,
             XYZWing => find_xyzwing,
+            RemotePairs => find_remote_pairs,
+            WWing => find_wwing,
+            WXYZWing => find_wxyz_wing,
             Swordfish => find_swordfish,
+            Jellyfish => find_jellyfish,
             RectangleElimination => find_rectangle_elimination,
             SingleDigitPattern => find_single_digit_pattern,
+            SimpleColoring => find_simple_coloring,
+            MultiColoring => find_multi_coloring,
+            FinnedFish => find_finned_fish,
+            UniqueRectangle => find_unique_rectangle,
+            AvoidableRectangle => find_avoidable_rectangle,
+            BUG => find_bug,
+            XChain => find_x_chain,
+            XYChain => find_xy_chain,
+            AlignedPairExclusion => find_aligned_pair_exclusion,
             Medusa => find_medusa,
+            FrankenFish => find_franken_fish,
+            MutantFish => find_mutant_fish,
+            KrakenFish => find_kraken_fish,
+            PatternOverlay => find_pattern_overlay,
+            AlignedTripleExclusion => find_aligned_triple_exclusion,
+            SueDeCoq => find_sue_de_coq,
+            ALSXZ => find_als_xz,
+            ALSXYWing => find_als_xy_wing,
+            DeathBlossom => find_death_blossom,
             AIC => find_general_aic,
+            AICWithALS => find_aic_with_als,
+            MultiFish => find_multi_fish,
+            ForcingChain => find_forcing_chain,
+            JuniorExocet => find_junior_exocet,
+            SKLoop => find_sk_loop,
+        }
+    }
+
+    pub fn get_difficulty(&self) -> Difficulty {
+        match self {
+            NakedSingle | HiddenSingle => Difficulty::Easy,
+            PointingSet | LockedCandidates | NakedSet | HiddenSet => Difficulty::Medium,
+            XWing | XYWing | XYZWing | RemotePairs | WWing | WXYZWing | Swordfish | Jellyfish
+            | RectangleElimination | SingleDigitPattern | SimpleColoring | MultiColoring
+            | FinnedFish | UniqueRectangle | AvoidableRectangle | BUG => Difficulty::Hard,
+            XChain
+            | XYChain
+            | AlignedPairExclusion
+            | Medusa
+            | FrankenFish
+            | MutantFish
+            | KrakenFish
+            | PatternOverlay
+            | AlignedTripleExclusion
+            | SueDeCoq
+            | ALSXZ
+            | ALSXYWing
+            | DeathBlossom
+            | AIC
+            | AICWithALS
+            | MultiFish
+            | ForcingChain
+            | JuniorExocet
+            | SKLoop => Difficulty::Diabolical,
+        }
+    }
+
+    // weight in tenths of a rating point, loosely following the Sudoku Explainer scale
+    pub fn get_weight(&self) -> u32 {
+        match self {
+            HiddenSingle => 15,
+            NakedSingle => 23,
+            PointingSet => 26,
+            LockedCandidates => 28,
+            NakedSet => 30,
+            HiddenSet => 31,
+            XWing => 32,
+            Swordfish => 38,
+            Jellyfish => 52,
+            XYWing => 42,
+            XYZWing => 44,
+            RemotePairs => 42,
+            WWing => 44,
+            WXYZWing => 50,
+            RectangleElimination => 45,
+            SingleDigitPattern => 45,
+            SimpleColoring => 45,
+            MultiColoring => 50,
+            FinnedFish => 40,
+            UniqueRectangle => 45,
+            AvoidableRectangle => 45,
+            BUG => 56,
+            XChain => 62,
+            XYChain => 62,
+            AlignedPairExclusion => 62,
+            Medusa => 60,
+            FrankenFish => 62,
+            MutantFish => 70,
+            KrakenFish => 75,
+            PatternOverlay => 75,
+            AlignedTripleExclusion => 75,
+            SueDeCoq => 70,
+            ALSXZ => 75,
+            ALSXYWing => 80,
+            DeathBlossom => 82,
+            AIC => 66,
+            AICWithALS => 85,
+            MultiFish => 88,
+            ForcingChain => 90,
+            JuniorExocet => 95,
+            SKLoop => 95,
         }
     }
 }
@@ -0,0 +1,240 @@
+use itertools::Itertools;
+
+use super::{
+    highlight::{Highlight, HighlightColor},
+    StrategyResult,
+};
+use crate::grid::{Cell, CellCandidate, Grid, UnitType};
+use crate::util::BitSet;
+
+use UnitType::{Col, MiniGrid, Row};
+
+// Sue de Coq: take some cells C of a box-line intersection holding at least |C| + 2 candidates.
+// Add a set of cells A from the rest of the line and a set B from the rest of the box, such that
+// A and B share no candidates and C, A and B together hold exactly as many candidates as cells.
+// Every one of those candidates is then placed exactly once in the union: the ones that can only
+// go in C or A are in the line, and the ones that can only go in C or B are in the box, so they
+// can be eliminated from the rest of that unit.
+pub fn find_sue_de_coq(grid: &Grid) -> Option<StrategyResult> {
+    for minigrid_n in 0..9 {
+        let minigrid = get_sorted_unit(grid, &MiniGrid, minigrid_n);
+
+        for (line_type, line_n) in get_lines_through(minigrid_n) {
+            let line = get_sorted_unit(grid, &line_type, line_n);
+
+            let intersection: Vec<Cell> = line
+                .iter()
+                .filter(|cell| minigrid.contains(cell))
+                .cloned()
+                .collect();
+
+            if intersection.len() < 2 {
+                continue;
+            }
+
+            let line_rest = difference(&line, &intersection);
+            let minigrid_rest = difference(&minigrid, &intersection);
+
+            for size in 2..=intersection.len() {
+                for core in intersection.iter().cloned().combinations(size) {
+                    let core_candidates = get_candidates(&core);
+
+                    if core_candidates.len() < size as u32 + 2 {
+                        continue;
+                    }
+
+                    let line_sets = get_supporting_sets(&line_rest, &core_candidates);
+                    let minigrid_sets = get_supporting_sets(&minigrid_rest, &core_candidates);
+
+                    for (line_set, line_candidates) in line_sets.iter() {
+                        for (minigrid_set, minigrid_candidates) in minigrid_sets.iter() {
+                            if !line_candidates.intersection(minigrid_candidates).is_empty() {
+                                continue;
+                            }
+
+                            let n_cells = core.len() + line_set.len() + minigrid_set.len();
+                            let all_candidates = core_candidates
+                                .union(line_candidates)
+                                .union(minigrid_candidates);
+
+                            if all_candidates.len() != n_cells as u32 {
+                                continue;
+                            }
+
+                            let in_line = line_candidates
+                                .union(&core_candidates.difference(minigrid_candidates));
+                            let in_minigrid = minigrid_candidates
+                                .union(&core_candidates.difference(line_candidates));
+
+                            let mut to_eliminate = get_eliminations(
+                                &line,
+                                &[core.as_slice(), line_set].concat(),
+                                &in_line,
+                            );
+                            to_eliminate.extend(get_eliminations(
+                                &minigrid,
+                                &[core.as_slice(), minigrid_set].concat(),
+                                &in_minigrid,
+                            ));
+
+                            to_eliminate.sort();
+                            to_eliminate.dedup();
+
+                            if to_eliminate.is_empty() {
+                                continue;
+                            }
+
+                            let highlights =
+                                make_highlights(&core, line_set, minigrid_set, &to_eliminate);
+
+                            return Some(StrategyResult::from(
+                                "Sue de Coq",
+                                vec![],
+                                to_eliminate,
+                                highlights,
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
+fn get_lines_through(minigrid_n: u32) -> Vec<(UnitType, u32)> {
+    let first_row = (minigrid_n / 3) * 3;
+    let first_col = (minigrid_n % 3) * 3;
+
+    (0..3)
+        .map(|k| (Row, first_row + k))
+        .chain((0..3).map(|k| (Col, first_col + k)))
+        .collect()
+}
+
+// sorted so that the patterns are always found in the same order
+fn get_sorted_unit(grid: &Grid, unit_type: &UnitType, num: u32) -> Vec<Cell> {
+    let mut cells: Vec<Cell> = grid.get_unit(unit_type, num).iter().cloned().collect();
+    cells.sort_by_key(|cell| (cell.get_row(), cell.get_col()));
+
+    cells
+}
+
+fn difference(cells: &[Cell], other: &[Cell]) -> Vec<Cell> {
+    cells
+        .iter()
+        .filter(|cell| !other.contains(cell))
+        .cloned()
+        .collect()
+}
+
+fn get_candidates(cells: &[Cell]) -> BitSet {
+    let mut candidates = BitSet::new();
+
+    for cell in cells.iter() {
+        candidates.extend(cell.get_candidates());
+    }
+
+    candidates
+}
+
+// Non-empty sets of cells sharing at least one candidate with the core, along with their candidates
+fn get_supporting_sets(cells: &[Cell], core_candidates: &BitSet) -> Vec<(Vec<Cell>, BitSet)> {
+    (1..=cells.len())
+        .flat_map(|size| cells.iter().cloned().combinations(size))
+        .map(|set| {
+            let candidates = get_candidates(&set);
+            (set, candidates)
+        })
+        .filter(|(_, candidates)| !candidates.intersection(core_candidates).is_empty())
+        .collect()
+}
+
+fn get_eliminations(unit: &[Cell], pattern: &[Cell], vals: &BitSet) -> Vec<CellCandidate> {
+    unit.iter()
+        .filter(|cell| !pattern.contains(cell))
+        .flat_map(|cell| {
+            cell.get_candidates()
+                .intersection(vals)
+                .iter()
+                .map(|val| CellCandidate::from_cell(cell, val))
+                .collect::<Vec<CellCandidate>>()
+        })
+        .collect()
+}
+
+fn make_highlights(
+    core: &[Cell],
+    line_set: &[Cell],
+    minigrid_set: &[Cell],
+    to_eliminate: &[CellCandidate],
+) -> Vec<Highlight> {
+    let mut highlights = vec![];
+
+    for cell in core.iter() {
+        highlights.push(Highlight::new_cell_hl(
+            cell.get_row(),
+            cell.get_col(),
+            HighlightColor::Yellow,
+        ));
+    }
+
+    for (cells, fg, bg) in [
+        (core, HighlightColor::NoteFg, HighlightColor::NoteBg),
+        (
+            line_set,
+            HighlightColor::NoteSecondaryFg,
+            HighlightColor::NoteSecondaryBg,
+        ),
+        (minigrid_set, HighlightColor::Black, HighlightColor::Orange),
+    ] {
+        for cell in cells.iter() {
+            for val in cell.get_candidates().iter() {
+                highlights.push(Highlight::new_candidate_hl(
+                    &CellCandidate::from_cell(cell, val),
+                    fg,
+                    bg,
+                ));
+            }
+        }
+    }
+
+    for cell_candidate in to_eliminate.iter() {
+        highlights.push(Highlight::new_candidate_hl(
+            cell_candidate,
+            HighlightColor::ElimFg,
+            HighlightColor::ElimBg,
+        ));
+    }
+
+    highlights
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_sue_de_coq() {
+        let bd =
+            "003500000000037000400809000004090300050600001600000000501900403800003097000040502";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let expected = vec![
+            CellCandidate::from(6, 1, 6),
+            CellCandidate::from(7, 4, 1),
+            CellCandidate::from(7, 4, 2),
+            CellCandidate::from(8, 5, 1),
+        ];
+
+        let sue_de_coq = find_sue_de_coq(&grid).unwrap();
+        let to_place = sue_de_coq.get_to_place().clone();
+        let mut to_eliminate = sue_de_coq.get_to_eliminate().clone();
+
+        to_eliminate.sort();
+
+        assert_eq!(Vec::<CellCandidate>::new(), to_place);
+        assert_eq!(expected, to_eliminate);
+    }
+}
@@ -0,0 +1,516 @@
+use itertools::Itertools;
+
+use super::{
+    highlight::{Highlight, HighlightColor},
+    StrategyResult,
+};
+use crate::grid::{Cell, CellCandidate, Grid, Region, UnitType};
+use crate::util::BitSet;
+
+use UnitType::{Col, MiniGrid, Row};
+
+// Four unsolved cells in two rows, two columns and two minigrids which all contain the candidates
+// a and b. If the cells could only be a or b the puzzle would have two solutions (the deadly
+// pattern), so assuming the puzzle is unique one of the cells must be something else.
+struct Rectangle {
+    // ordered so that corners i and 3 - i are diagonal
+    corners: [Cell; 4],
+    a: u32,
+    b: u32,
+}
+
+impl Rectangle {
+    fn deadly(&self) -> BitSet {
+        BitSet::from(&[self.a, self.b])
+    }
+
+    fn is_floor(&self, i: usize) -> bool {
+        *self.corners[i].get_candidates() == self.deadly()
+    }
+
+    // indices of the corners with candidates other than a and b
+    fn roof(&self) -> Vec<usize> {
+        (0..4).filter(|&i| !self.is_floor(i)).collect()
+    }
+
+    fn extras(&self, i: usize) -> BitSet {
+        self.corners[i].get_candidates().difference(&self.deadly())
+    }
+}
+
+pub fn find_unique_rectangle(grid: &Grid) -> Option<StrategyResult> {
+    let rectangles = get_rectangles(grid);
+
+    let finders = [type_1, type_2, type_3, type_4, type_5, type_6, hidden];
+
+    for finder in finders.iter() {
+        for rect in rectangles.iter() {
+            if let Some(res) = finder(grid, rect) {
+                return Some(res);
+            }
+        }
+    }
+
+    None
+}
+
+fn get_rectangles(grid: &Grid) -> Vec<Rectangle> {
+    let mut rectangles = vec![];
+
+    for (r1, r2) in (0..9).tuple_combinations::<(_, _)>() {
+        for (c1, c2) in (0..9).tuple_combinations::<(_, _)>() {
+            // the rectangle has to span exactly two minigrids
+            if (r1 / 3 == r2 / 3) == (c1 / 3 == c2 / 3) {
+                continue;
+            }
+
+            let coords = [(r1, c1), (r1, c2), (r2, c1), (r2, c2)];
+
+            if coords.iter().any(|&(r, c)| grid.get_placed(r, c) != 0) {
+                continue;
+            }
+
+            let corners = coords.map(|(r, c)| Cell::from(r, c, grid.get_candidates(r, c)));
+
+            let common = corners
+                .iter()
+                .map(|cell| cell.get_candidates().clone())
+                .reduce(|acc, cands| acc.intersection(&cands))
+                .unwrap();
+
+            for pair in common.iter().combinations(2) {
+                rectangles.push(Rectangle {
+                    corners: corners.clone(),
+                    a: pair[0],
+                    b: pair[1],
+                });
+            }
+        }
+    }
+
+    rectangles
+}
+
+// three corners are exactly ab, so a and b can be removed from the fourth
+fn type_1(_grid: &Grid, rect: &Rectangle) -> Option<StrategyResult> {
+    let roof = rect.roof();
+
+    if roof.len() != 1 {
+        return None;
+    }
+
+    let cell = &rect.corners[roof[0]];
+
+    let to_eliminate = vec![
+        CellCandidate::from_cell(cell, rect.a),
+        CellCandidate::from_cell(cell, rect.b),
+    ];
+
+    let highlights = make_highlights(rect, &to_eliminate);
+
+    Some(StrategyResult::from(
+        "Unique Rectangle Type 1",
+        vec![],
+        to_eliminate,
+        highlights,
+    ))
+}
+
+// two corners in a line are ab, the other two are abx, so one of them must be x
+fn type_2(grid: &Grid, rect: &Rectangle) -> Option<StrategyResult> {
+    let roof = rect.roof();
+
+    if roof.len() != 2 || roof[0] + roof[1] == 3 {
+        return None;
+    }
+
+    single_extra(grid, rect, &roof, "Unique Rectangle Type 2")
+}
+
+// two corners in a line are ab, and the extra candidates of the other two form a naked set with
+// other cells in a unit they share
+fn type_3(grid: &Grid, rect: &Rectangle) -> Option<StrategyResult> {
+    let roof = rect.roof();
+
+    if roof.len() != 2 || roof[0] + roof[1] == 3 {
+        return None;
+    }
+
+    let roof_cells = Region::from(&[rect.corners[roof[0]].clone(), rect.corners[roof[1]].clone()]);
+    let extras = rect.extras(roof[0]).union(&rect.extras(roof[1]));
+
+    for unit in get_shared_units(grid, &rect.corners[roof[0]], &rect.corners[roof[1]]) {
+        let others = unit.difference(&roof_cells);
+
+        // the roof acts as a single cell with the extra candidates
+        for size in 1..4 {
+            for combination in others.iter().cloned().combinations(size) {
+                let mut set_candidates = extras.clone();
+
+                for cell in combination.iter() {
+                    set_candidates.extend(cell.get_candidates());
+                }
+
+                if set_candidates.len() != size as u32 + 1 {
+                    continue;
+                }
+
+                let mut to_eliminate = vec![];
+
+                for cell in others.difference(&Region::from(&combination)).iter() {
+                    for val in cell.get_candidates().intersection(&set_candidates).iter() {
+                        to_eliminate.push(CellCandidate::from_cell(cell, val));
+                    }
+                }
+
+                if to_eliminate.is_empty() {
+                    continue;
+                }
+
+                let mut highlights = make_highlights(rect, &to_eliminate);
+
+                for cell in combination.iter() {
+                    for val in cell.get_candidates().iter() {
+                        highlights.push(Highlight::new_candidate_hl(
+                            &CellCandidate::from_cell(cell, val),
+                            HighlightColor::NoteSecondaryFg,
+                            HighlightColor::NoteSecondaryBg,
+                        ));
+                    }
+                }
+
+                return Some(StrategyResult::from(
+                    "Unique Rectangle Type 3",
+                    vec![],
+                    to_eliminate,
+                    highlights,
+                ));
+            }
+        }
+    }
+
+    None
+}
+
+// two corners in a line are ab, and the other two are the only cells in a shared unit that can be
+// a, so neither of them can be b
+fn type_4(grid: &Grid, rect: &Rectangle) -> Option<StrategyResult> {
+    let roof = rect.roof();
+
+    if roof.len() != 2 || roof[0] + roof[1] == 3 {
+        return None;
+    }
+
+    let roof_a = &rect.corners[roof[0]];
+    let roof_b = &rect.corners[roof[1]];
+
+    for unit in get_shared_units(grid, roof_a, roof_b) {
+        for (x, y) in [(rect.a, rect.b), (rect.b, rect.a)] {
+            if unit.scan(x).len() != 2 {
+                continue;
+            }
+
+            let to_eliminate = vec![
+                CellCandidate::from_cell(roof_a, y),
+                CellCandidate::from_cell(roof_b, y),
+            ];
+
+            let highlights = make_highlights(rect, &to_eliminate);
+
+            return Some(StrategyResult::from(
+                "Unique Rectangle Type 4",
+                vec![],
+                to_eliminate,
+                highlights,
+            ));
+        }
+    }
+
+    None
+}
+
+// like type 2, but with the abx corners diagonal or with three abx corners
+fn type_5(grid: &Grid, rect: &Rectangle) -> Option<StrategyResult> {
+    let roof = rect.roof();
+
+    let is_type_5 = match roof.len() {
+        2 => roof[0] + roof[1] == 3,
+        3 => true,
+        _ => false,
+    };
+
+    if !is_type_5 {
+        return None;
+    }
+
+    single_extra(grid, rect, &roof, "Unique Rectangle Type 5")
+}
+
+// two diagonal corners are ab, and a is strongly linked in both rows and both columns of the
+// rectangle, so the other two corners cannot be a
+fn type_6(grid: &Grid, rect: &Rectangle) -> Option<StrategyResult> {
+    let roof = rect.roof();
+
+    if roof.len() != 2 || roof[0] + roof[1] != 3 {
+        return None;
+    }
+
+    for x in [rect.a, rect.b] {
+        if !is_strong_in_lines(grid, rect, x) {
+            continue;
+        }
+
+        let to_eliminate: Vec<CellCandidate> = roof
+            .iter()
+            .map(|&i| CellCandidate::from_cell(&rect.corners[i], x))
+            .collect();
+
+        let highlights = make_highlights(rect, &to_eliminate);
+
+        return Some(StrategyResult::from(
+            "Unique Rectangle Type 6",
+            vec![],
+            to_eliminate,
+            highlights,
+        ));
+    }
+
+    None
+}
+
+// one corner is ab, and a is strongly linked in the row and column of the opposite corner, so
+// the opposite corner cannot be b
+fn hidden(grid: &Grid, rect: &Rectangle) -> Option<StrategyResult> {
+    for floor in 0..4 {
+        if !rect.is_floor(floor) {
+            continue;
+        }
+
+        let opposite = &rect.corners[3 - floor];
+
+        if rect.is_floor(3 - floor) {
+            continue;
+        }
+
+        for (x, y) in [(rect.a, rect.b), (rect.b, rect.a)] {
+            let row = grid.get_unit_containing(&Row, opposite).scan(x);
+            let col = grid.get_unit_containing(&Col, opposite).scan(x);
+
+            if row.len() != 2 || col.len() != 2 {
+                continue;
+            }
+
+            let to_eliminate = vec![CellCandidate::from_cell(opposite, y)];
+
+            let highlights = make_highlights(rect, &to_eliminate);
+
+            return Some(StrategyResult::from(
+                "Hidden Unique Rectangle",
+                vec![],
+                to_eliminate,
+                highlights,
+            ));
+        }
+    }
+
+    None
+}
+
+// the roof corners all have the same single extra candidate x, so one of them must be x
+fn single_extra(
+    grid: &Grid,
+    rect: &Rectangle,
+    roof: &[usize],
+    name: &str,
+) -> Option<StrategyResult> {
+    let extras = rect.extras(roof[0]);
+
+    if extras.len() != 1 || roof.iter().any(|&i| rect.extras(i) != extras) {
+        return None;
+    }
+
+    let x = extras.get_smallest();
+
+    let sees_all = roof
+        .iter()
+        .map(|&i| grid.get_cells_that_see(&rect.corners[i], false))
+        .reduce(|acc, region| acc.intersection(&region))
+        .unwrap()
+        .scan(x);
+
+    if sees_all.is_empty() {
+        return None;
+    }
+
+    let to_eliminate: Vec<CellCandidate> = sees_all
+        .iter()
+        .map(|cell| CellCandidate::from_cell(cell, x))
+        .collect();
+
+    let highlights = make_highlights(rect, &to_eliminate);
+
+    Some(StrategyResult::from(name, vec![], to_eliminate, highlights))
+}
+
+fn is_strong_in_lines(grid: &Grid, rect: &Rectangle, val: u32) -> bool {
+    let (r1, c1) = (rect.corners[0].get_row(), rect.corners[0].get_col());
+    let (r2, c2) = (rect.corners[3].get_row(), rect.corners[3].get_col());
+
+    [(Row, r1), (Row, r2), (Col, c1), (Col, c2)]
+        .iter()
+        .all(|(unit_type, k)| grid.get_unit(unit_type, *k).scan(val).len() == 2)
+}
+
+fn get_shared_units(grid: &Grid, cell1: &Cell, cell2: &Cell) -> Vec<Region> {
+    let mut units = vec![];
+
+    if cell1.get_row() == cell2.get_row() {
+        units.push(grid.get_unit_containing(&Row, cell1));
+    }
+
+    if cell1.get_col() == cell2.get_col() {
+        units.push(grid.get_unit_containing(&Col, cell1));
+    }
+
+    if cell1.get_minigrid_n() == cell2.get_minigrid_n() {
+        units.push(grid.get_unit_containing(&MiniGrid, cell1));
+    }
+
+    units
+}
+
+fn make_highlights(rect: &Rectangle, to_eliminate: &[CellCandidate]) -> Vec<Highlight> {
+    let mut highlights = vec![];
+
+    for cell in rect.corners.iter() {
+        highlights.push(Highlight::new_cell_hl(
+            cell.get_row(),
+            cell.get_col(),
+            HighlightColor::Orange,
+        ));
+
+        for val in [rect.a, rect.b] {
+            highlights.push(Highlight::new_candidate_hl(
+                &CellCandidate::from_cell(cell, val),
+                HighlightColor::NoteFg,
+                HighlightColor::NoteBg,
+            ));
+        }
+    }
+
+    for cell_candidate in to_eliminate.iter() {
+        highlights.push(Highlight::new_candidate_hl(
+            cell_candidate,
+            HighlightColor::ElimFg,
+            HighlightColor::ElimBg,
+        ));
+    }
+
+    highlights
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(bd: &str, name: &str, expected: Vec<CellCandidate>) {
+        let grid = Grid::from_str(bd).unwrap();
+
+        check_grid(&grid, name, expected);
+    }
+
+    fn check_grid(grid: &Grid, name: &str, mut expected: Vec<CellCandidate>) {
+        let unique_rectangle = find_unique_rectangle(grid).unwrap();
+        let to_place = unique_rectangle.get_to_place().clone();
+        let mut to_eliminate = unique_rectangle.get_to_eliminate().clone();
+
+        expected.sort();
+        to_eliminate.sort();
+
+        assert_eq!(name, unique_rectangle.get_name());
+        assert_eq!(Vec::<CellCandidate>::new(), to_place);
+        assert_eq!(expected, to_eliminate);
+    }
+
+    #[test]
+    fn test_unique_rectangle_type_1() {
+        let bd =
+            "005604070090003106601080040906035804000968007003140069047890600569300400028456790";
+
+        let expected = vec![CellCandidate::from(6, 8, 1), CellCandidate::from(6, 8, 3)];
+
+        check(bd, "Unique Rectangle Type 1", expected);
+    }
+
+    #[test]
+    fn test_unique_rectangle_type_2() {
+        let bd =
+            "003600000004010200758249631000401507070020040409507000532768914001050700000004000";
+
+        let expected = vec![
+            CellCandidate::from(7, 0, 8),
+            CellCandidate::from(7, 1, 8),
+            CellCandidate::from(8, 6, 8),
+            CellCandidate::from(8, 7, 8),
+            CellCandidate::from(8, 8, 8),
+        ];
+
+        check(bd, "Unique Rectangle Type 2", expected);
+    }
+
+    #[test]
+    fn test_unique_rectangle_type_3() {
+        let bd =
+            "000609008584200000926083001490000005865492173700000094000120087008900302200835000";
+
+        let expected = vec![CellCandidate::from(3, 2, 2)];
+
+        check(bd, "Unique Rectangle Type 3", expected);
+    }
+
+    #[test]
+    fn test_unique_rectangle_type_4() {
+        let bd =
+            "200010007000207000057000020005020408001549203342768001079854030028000000634002085";
+
+        let expected = vec![CellCandidate::from(1, 6, 9), CellCandidate::from(1, 7, 9)];
+
+        check(bd, "Unique Rectangle Type 4", expected);
+    }
+
+    #[test]
+    fn test_unique_rectangle_type_5() {
+        let bd =
+            "409300085781564329030098070000039548003800962890600713910056037352080090600903050";
+        let mut grid = Grid::from_str(bd).unwrap();
+
+        // eliminated earlier in the solve, leaving 46 in r3c9 and r8c7 with 146 in the diagonal
+        // corners r3c7 and r8c9
+        grid.clear_candidate(&CellCandidate::from(2, 8, 1));
+        grid.clear_candidate(&CellCandidate::from(7, 6, 1));
+
+        let expected = vec![CellCandidate::from(8, 6, 1)];
+
+        check_grid(&grid, "Unique Rectangle Type 5", expected);
+    }
+
+    #[test]
+    fn test_unique_rectangle_type_6() {
+        let bd =
+            "000030008000804160080006009005713402003000607724698500108300270057200800200087000";
+
+        let expected = vec![CellCandidate::from(3, 0, 8), CellCandidate::from(4, 7, 8)];
+
+        check(bd, "Unique Rectangle Type 6", expected);
+    }
+
+    #[test]
+    fn test_hidden_unique_rectangle() {
+        let bd =
+            "000000000305904681040010030200000007007163420000207003020801076816000302009000100";
+
+        let expected = vec![CellCandidate::from(0, 8, 5)];
+
+        check(bd, "Hidden Unique Rectangle", expected);
+    }
+}
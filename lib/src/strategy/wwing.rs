@@ -0,0 +1,171 @@
+use itertools::Itertools;
+
+use super::{
+    highlight::{Highlight, HighlightColor},
+    StrategyResult,
+};
+use crate::grid::{Cell, CellCandidate, Grid, UnitType};
+
+use UnitType::{Col, MiniGrid, Row};
+
+// Two bivalue cells P and Q with the same candidates AB, which can't see each other. If A is the
+// only candidate of a unit in two cells, one seeing P and the other seeing Q, then P and Q can't
+// both be A, so one of them has to be B.
+pub fn find_wwing(grid: &Grid) -> Option<StrategyResult> {
+    let mut bivalue_cells: Vec<Cell> = grid.get_nvalue_cells(2).iter().cloned().collect();
+    bivalue_cells.sort_by_key(|cell| (cell.get_row(), cell.get_col()));
+
+    for (p, q) in bivalue_cells.iter().tuple_combinations::<(_, _)>() {
+        if p.get_candidates() != q.get_candidates() || p.can_see(q) {
+            continue;
+        }
+
+        for a in p.get_candidates().iter() {
+            let b = p.get_candidates().iter().find(|&val| val != a).unwrap();
+
+            let Some((x, y)) = find_strong_link(grid, p, q, a) else {
+                continue;
+            };
+
+            let sees_both = grid
+                .get_cells_that_see(p, false)
+                .intersection(&grid.get_cells_that_see(q, false))
+                .scan(b);
+
+            if sees_both.is_empty() {
+                continue;
+            }
+
+            let to_eliminate: Vec<CellCandidate> = sees_both
+                .iter()
+                .map(|cell| CellCandidate::from_cell(cell, b))
+                .collect();
+
+            let highlights = make_highlights(p, q, &x, &y, a, b, &to_eliminate);
+
+            return Some(StrategyResult::from(
+                "W-Wing",
+                vec![],
+                to_eliminate,
+                highlights,
+            ));
+        }
+    }
+
+    None
+}
+
+// A unit where val can only go in two cells, other than p and q, with the first seeing p and the
+// second seeing q
+fn find_strong_link(grid: &Grid, p: &Cell, q: &Cell, val: u32) -> Option<(Cell, Cell)> {
+    for unit_type in &[Row, Col, MiniGrid] {
+        for k in 0..9 {
+            let cells = grid.get_unit(unit_type, k).scan(val);
+
+            if cells.len() != 2 {
+                continue;
+            }
+
+            let (x, y) = cells.iter().cloned().collect_tuple().unwrap();
+
+            if [&x, &y].iter().any(|cell| *cell == p || *cell == q) {
+                continue;
+            }
+
+            if x.can_see(p) && y.can_see(q) {
+                return Some((x, y));
+            }
+
+            if y.can_see(p) && x.can_see(q) {
+                return Some((y, x));
+            }
+        }
+    }
+
+    None
+}
+
+fn make_highlights(
+    p: &Cell,
+    q: &Cell,
+    x: &Cell,
+    y: &Cell,
+    a: u32,
+    b: u32,
+    to_eliminate: &[CellCandidate],
+) -> Vec<Highlight> {
+    let mut highlights = vec![
+        Highlight::new_cell_hl(p.get_row(), p.get_col(), HighlightColor::Orange),
+        Highlight::new_cell_hl(q.get_row(), q.get_col(), HighlightColor::Orange),
+        Highlight::new_cell_hl(x.get_row(), x.get_col(), HighlightColor::Yellow),
+        Highlight::new_cell_hl(y.get_row(), y.get_col(), HighlightColor::Yellow),
+    ];
+
+    let chain: Vec<CellCandidate> = [p, x, y, q]
+        .iter()
+        .map(|cell| CellCandidate::from_cell(cell, a))
+        .collect();
+
+    for cell_candidate in chain.iter() {
+        highlights.push(Highlight::new_candidate_hl(
+            cell_candidate,
+            HighlightColor::NoteSecondaryFg,
+            HighlightColor::NoteSecondaryBg,
+        ));
+    }
+
+    // weak links into and out of the strong link on a
+    for (i, (start, end)) in chain.iter().tuple_windows().enumerate() {
+        highlights.push(Highlight::new_line_hl(
+            start,
+            end,
+            HighlightColor::Red,
+            i != 1,
+        ));
+    }
+
+    for cell in [p, q] {
+        highlights.push(Highlight::new_candidate_hl(
+            &CellCandidate::from_cell(cell, b),
+            HighlightColor::NoteFg,
+            HighlightColor::NoteBg,
+        ));
+    }
+
+    for cell_candidate in to_eliminate.iter() {
+        highlights.push(Highlight::new_candidate_hl(
+            cell_candidate,
+            HighlightColor::ElimFg,
+            HighlightColor::ElimBg,
+        ));
+    }
+
+    highlights
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_wwing() {
+        let bd =
+            "841060572320007146760410030030000400176040803084000010050094081410700059098030704";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let expected = vec![
+            CellCandidate::from(6, 6, 2),
+            CellCandidate::from(8, 3, 2),
+            CellCandidate::from(8, 5, 2),
+        ];
+
+        let wwing = find_wwing(&grid).unwrap();
+        let to_place = wwing.get_to_place().clone();
+        let mut to_eliminate = wwing.get_to_eliminate().clone();
+
+        to_eliminate.sort();
+
+        assert_eq!(Vec::<CellCandidate>::new(), to_place);
+        assert_eq!(expected, to_eliminate);
+    }
+}
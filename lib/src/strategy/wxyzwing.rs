@@ -0,0 +1,209 @@
+use itertools::Itertools;
+
+use std::collections::HashSet;
+
+use super::{
+    highlight::{Highlight, HighlightColor},
+    StrategyResult,
+};
+use crate::grid::{Cell, CellCandidate, Grid, Region, UnitType};
+use crate::util::BitSet;
+
+use UnitType::{Col, MiniGrid, Row};
+
+pub fn find_wxyz_wing(grid: &Grid) -> Option<StrategyResult> {
+    (4..6).find_map(|size| find_bent_set(grid, size))
+}
+
+fn get_name(size: usize) -> &'static str {
+    match size {
+        4 => "WXYZ-Wing",
+        5 => "VWXYZ-Wing",
+        _ => unreachable!(),
+    }
+}
+
+// A bent set is made of size cells with size candidates between them, spread over a minigrid and a
+// line crossing it. A candidate is restricted if all the cells of the set that could hold it see
+// each other, so it's in at most one of them. If all but one candidate Z are restricted, the other
+// candidates can fill at most size - 1 of the cells, so Z has to be in the set and it can be
+// eliminated from anywhere that sees every cell of the set that could hold it.
+fn find_bent_set(grid: &Grid, size: usize) -> Option<StrategyResult> {
+    let mut seen = HashSet::new();
+
+    for minigrid_n in 0..9 {
+        let minigrid = grid.get_unit(&MiniGrid, minigrid_n);
+
+        let (cr, cc) = ((minigrid_n / 3) * 3, (minigrid_n % 3) * 3);
+
+        let lines = (cr..cr + 3)
+            .map(|row| grid.get_unit(&Row, row))
+            .chain((cc..cc + 3).map(|col| grid.get_unit(&Col, col)));
+
+        for line in lines {
+            let mut cells: Vec<Cell> = minigrid
+                .union(&line)
+                .iter()
+                .filter(|cell| cell.get_candidates().len() <= size as u32)
+                .cloned()
+                .collect();
+            cells.sort_by_key(|cell| (cell.get_row(), cell.get_col()));
+
+            for combination in cells.into_iter().combinations(size) {
+                // all in one unit would be a naked set
+                if Region::from(&combination).all_in_unit().is_some() {
+                    continue;
+                }
+
+                let mut candidates = BitSet::new();
+
+                for cell in combination.iter() {
+                    candidates.extend(cell.get_candidates());
+                }
+
+                if candidates.len() != size as u32 {
+                    continue;
+                }
+
+                let key: Vec<(u32, u32)> = combination
+                    .iter()
+                    .map(|cell| (cell.get_row(), cell.get_col()))
+                    .collect();
+
+                if !seen.insert(key) {
+                    continue;
+                }
+
+                let unrestricted: Vec<u32> = candidates
+                    .iter()
+                    .filter(|&val| !is_restricted(&combination, val))
+                    .collect();
+
+                if unrestricted.len() != 1 {
+                    continue;
+                }
+
+                let z = unrestricted[0];
+
+                let z_cells: Vec<&Cell> = combination
+                    .iter()
+                    .filter(|cell| cell.get_candidates().contains(z))
+                    .collect();
+
+                let to_eliminate: Vec<CellCandidate> = grid
+                    .as_region()
+                    .scan(z)
+                    .iter()
+                    .filter(|cell| !combination.contains(cell))
+                    .filter(|cell| z_cells.iter().all(|z_cell| cell.can_see(z_cell)))
+                    .map(|cell| CellCandidate::from_cell(cell, z))
+                    .collect();
+
+                if to_eliminate.is_empty() {
+                    continue;
+                }
+
+                let highlights = make_highlights(&combination, z, &to_eliminate);
+
+                return Some(StrategyResult::from(
+                    get_name(size),
+                    vec![],
+                    to_eliminate,
+                    highlights,
+                ));
+            }
+        }
+    }
+
+    None
+}
+
+fn is_restricted(cells: &[Cell], val: u32) -> bool {
+    cells
+        .iter()
+        .filter(|cell| cell.get_candidates().contains(val))
+        .tuple_combinations()
+        .all(|(a, b)| a.can_see(b))
+}
+
+fn make_highlights(cells: &[Cell], z: u32, to_eliminate: &[CellCandidate]) -> Vec<Highlight> {
+    let mut highlights = vec![];
+
+    for cell in cells.iter() {
+        highlights.push(Highlight::new_cell_hl(
+            cell.get_row(),
+            cell.get_col(),
+            HighlightColor::Orange,
+        ));
+
+        for val in cell.get_candidates().iter() {
+            let (fg, bg) = if val == z {
+                (HighlightColor::NoteFg, HighlightColor::NoteBg)
+            } else {
+                (
+                    HighlightColor::NoteSecondaryFg,
+                    HighlightColor::NoteSecondaryBg,
+                )
+            };
+
+            highlights.push(Highlight::new_candidate_hl(
+                &CellCandidate::from_cell(cell, val),
+                fg,
+                bg,
+            ));
+        }
+    }
+
+    for cell_candidate in to_eliminate.iter() {
+        highlights.push(Highlight::new_candidate_hl(
+            cell_candidate,
+            HighlightColor::ElimFg,
+            HighlightColor::ElimBg,
+        ));
+    }
+
+    highlights
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_wxyz_wing() {
+        let bd =
+            "000060570320007040700410000030000400106000803004000010000094001010700059098030000";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let expected = vec![CellCandidate::from(2, 1, 5), CellCandidate::from(3, 2, 5)];
+
+        let wxyz_wing = find_wxyz_wing(&grid).unwrap();
+        let to_place = wxyz_wing.get_to_place().clone();
+        let mut to_eliminate = wxyz_wing.get_to_eliminate().clone();
+
+        to_eliminate.sort();
+
+        assert_eq!("WXYZ-Wing", wxyz_wing.get_name());
+        assert_eq!(Vec::<CellCandidate>::new(), to_place);
+        assert_eq!(expected, to_eliminate);
+    }
+
+    #[test]
+    fn test_find_vwxyz_wing() {
+        let bd =
+            "964135200217968340083002169735681924040293600692050831320500496476829513059300702";
+        let grid = Grid::from_str(bd).unwrap();
+
+        let expected = vec![CellCandidate::from(6, 2, 1), CellCandidate::from(8, 4, 1)];
+
+        let vwxyz_wing = find_wxyz_wing(&grid).unwrap();
+        let to_place = vwxyz_wing.get_to_place().clone();
+        let mut to_eliminate = vwxyz_wing.get_to_eliminate().clone();
+
+        to_eliminate.sort();
+
+        assert_eq!("VWXYZ-Wing", vwxyz_wing.get_name());
+        assert_eq!(Vec::<CellCandidate>::new(), to_place);
+        assert_eq!(expected, to_eliminate);
+    }
+}
@@ -1,3 +1,5 @@
 mod bitset;
+mod rng;
 
 pub use bitset::BitSet;
+pub use rng::Rng;
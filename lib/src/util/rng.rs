@@ -0,0 +1,40 @@
+// Small xorshift64* generator, used so that puzzle generation is reproducible from a seed without
+// pulling in an external crate (which would also need extra setup for the wasm target).
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn from_seed(seed: u64) -> Rng {
+        let state = seed ^ 0x9e37_79b9_7f4a_7c15;
+
+        // the state of xorshift must never be zero
+        if state == 0 {
+            Rng(0x9e37_79b9_7f4a_7c15)
+        } else {
+            Rng(state)
+        }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let mut x = self.0;
+
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+
+        self.0 = x;
+
+        x.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    // uniform value in 0..n
+    pub fn gen_range(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.gen_range(i + 1);
+            items.swap(i, j);
+        }
+    }
+}
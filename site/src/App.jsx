@@ -16,7 +16,8 @@ function App() {
   function setGridToBd() {
     const grid = wasm.get_grid_from_bd_str(bd);
 
-    if (grid != undefined) {
+    // only accept puzzles with exactly one solution
+    if (grid != undefined && wasm.count_solutions_of_bd_str(bd, 2) == 1) {
       setGridIdx(0);
       setGridHistory([grid]);
       setSelected(null);
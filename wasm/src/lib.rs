@@ -1,6 +1,7 @@
 use wasm_bindgen::prelude::*;
 
 use lib::{
+    brute_force,
     grid::Grid,
     solver::Solver,
 };
@@ -17,6 +18,14 @@ pub fn get_grid_from_bd_str(bd: &str) -> JsValue {
     serde_wasm_bindgen::to_value(&grid).unwrap()
 }
 
+#[wasm_bindgen]
+pub fn count_solutions_of_bd_str(bd: &str, limit: usize) -> usize {
+    match Grid::from_str(bd) {
+        Ok(grid) => brute_force::count_solutions(&grid, limit),
+        Err(_) => 0,
+    }
+}
+
 #[wasm_bindgen]
 pub fn solve_step(grid_obj: JsValue) -> JsValue {
     let grid: Grid = serde_wasm_bindgen::from_value(grid_obj).unwrap();